@@ -1,25 +1,39 @@
 use std::{
 	mem,
 	sync::atomic::{AtomicBool, Ordering},
+	time::{Duration, Instant},
 };
 
 use starlight_macros::model;
 use tracing::instrument;
+use twilight_gateway::Intents;
+use twilight_http::request::AttachmentFile;
 use twilight_model::{
 	application::{
-		callback::{Autocomplete, InteractionResponse},
+		callback::{Autocomplete, CallbackData, InteractionResponse},
 		command::Command,
-		interaction::{application_command::CommandData, ApplicationCommand, InteractionType},
+		interaction::{
+			application_command::CommandData, ApplicationCommand, InteractionType,
+			MessageComponentInteraction,
+		},
 	},
 	channel::Message,
+	id::{
+		marker::{GuildMarker, UserMarker},
+		Id,
+	},
 };
-use twilight_util::builder::command::CommandBuilder;
 
-use super::Helpers;
+use super::{rate_limit::retry_message, Helpers};
 use crate::{
 	prelude::*,
+	settings::{GuildSettings, Tables},
 	slashies::{
-		commands::{Crate, Ping, Tag},
+		commands::{
+			Asm, Clippy, Crate, Eval, Expand, Explain, Fmt, FormatCode, Miri, Ping, Play, RunCode,
+			Settings, Status, Tag, Test,
+		},
+		components::{ExplainMenu, PlaygroundAction},
 		DefineCommand, SlashCommand, SlashData,
 	},
 	state::{Context, QuickAccess},
@@ -27,7 +41,7 @@ use crate::{
 
 static INITIALIZED: AtomicBool = AtomicBool::new(false);
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[must_use = "an InteractionsHelper does nothing if not used"]
 pub struct InteractionsHelper(Helpers);
 
@@ -36,7 +50,7 @@ impl InteractionsHelper {
 		Self(helpers)
 	}
 
-	pub async fn init(self) -> Result<()> {
+	pub async fn init(&self) -> Result<()> {
 		if INITIALIZED.load(Ordering::SeqCst) {
 			return Ok(());
 		}
@@ -62,55 +76,176 @@ impl InteractionsHelper {
 	}
 
 	#[instrument(skip(self, command), fields(command.name = %command.data.name, command.guild_id))]
-	pub async fn handle(self, command: ApplicationCommand) {
-		if let Some(slashie) = Self::match_command(command.data.name.as_str(), command.data.clone())
-		{
-			let data = SlashData::new(command.clone());
-			match command.kind {
-				InteractionType::ApplicationCommand => {
-					if let Err(e) = slashie.run(self, data).await {
-						event!(
-							Level::ERROR,
-							error = &*e.root_cause(),
-							"error running command"
-						);
+	pub async fn handle(&self, command: ApplicationCommand) {
+		let slashie = match Self::match_command(command.data.name.as_str(), command.data.clone()) {
+			Some(Ok(slashie)) => slashie,
+			// the options don't match what the command expects, usually because discord hasn't
+			// picked up a change to the command yet
+			Some(Err(e)) => {
+				event!(
+					Level::ERROR,
+					error = &*e.root_cause(),
+					"error parsing command"
+				);
 
-						let mut err_data = SlashData::new(command);
+				if command.kind == InteractionType::ApplicationCommand {
+					self.respond_error(
+						command,
+						"this command couldn't be read, try again in a few minutes",
+					)
+					.await;
+				}
 
-						err_data
-							.message("an error occurred running the interaction".to_owned())
-							.ephemeral();
+				return;
+			}
+			None => {
+				event!(Level::WARN, "received unregistered command");
+				return;
+			}
+		};
 
-						if self.raw_get(&err_data).await.is_err() {
-							self.respond(&mut err_data).await.unwrap();
-						} else {
-							self.update(&mut err_data).await.unwrap();
-						}
-					}
-				}
-				InteractionType::ApplicationCommandAutocomplete => {
-					if let Err(e) = slashie.autocomplete(self, data).await {
+		let mut data = SlashData::new(command.clone());
+		match command.kind {
+			InteractionType::ApplicationCommand => {
+				if self
+					.config()
+					.disabled_commands
+					.iter()
+					.any(|name| *name == command.data.name)
+				{
+					data.message("this command is disabled".to_owned())
+						.ephemeral();
+
+					if let Err(e) = self.respond(&mut data).await {
 						event!(
 							Level::ERROR,
-							error = &*e.root_cause(),
-							"error running autocomplete"
+							error = %e,
+							"error responding to disabled command"
 						);
 					}
+
+					return;
+				}
+
+				if let Err(e) = slashie.run(self.clone(), data).await {
+					event!(
+						Level::ERROR,
+						error = &*e.root_cause(),
+						"error running command"
+					);
+
+					self.respond_error(command, "an error occurred running the interaction")
+						.await;
 				}
-				_ => {}
 			}
+			InteractionType::ApplicationCommandAutocomplete => {
+				if let Err(e) = slashie.autocomplete(self.clone(), data).await {
+					event!(
+						Level::ERROR,
+						error = &*e.root_cause(),
+						"error running autocomplete"
+					);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	async fn respond_error(&self, command: ApplicationCommand, message: &str) {
+		let mut data = SlashData::new(command);
+
+		data.message(message.to_owned()).ephemeral();
+
+		let result = if self.raw_get(&data).await.is_err() {
+			self.respond(&mut data).await.into_diagnostic()
 		} else {
-			event!(Level::WARN, "received unregistered command");
+			self.update(&mut data).await
+		};
+
+		if let Err(e) = result {
+			event!(
+				Level::ERROR,
+				error = &*e.root_cause(),
+				"error responding with an error"
+			);
+		}
+	}
+
+	#[instrument(skip(self, component), fields(component.custom_id = %component.data.custom_id))]
+	pub async fn handle_component(&self, component: MessageComponentInteraction) {
+		let result = match component.data.custom_id.split(':').next() {
+			Some(PlaygroundAction::PREFIX) => {
+				match component.data.custom_id.parse::<PlaygroundAction>() {
+					Ok(action) => action.run(self, component).await,
+					Err(e) => Err(e).into_diagnostic(),
+				}
+			}
+			Some(ExplainMenu::PREFIX) => ExplainMenu::run(self, component).await,
+			_ => {
+				event!(Level::WARN, "received unregistered component");
+				return;
+			}
+		};
+
+		if let Err(e) = result {
+			event!(
+				Level::ERROR,
+				error = &*e.root_cause(),
+				"error running component"
+			);
+		}
+	}
+
+	pub async fn check_rate_limit(
+		&self,
+		user_id: Id<UserMarker>,
+		guild_id: Option<Id<GuildMarker>>,
+	) -> Result<(), Duration> {
+		if self.config().owners.contains(&user_id) {
+			return Ok(());
+		}
+
+		let defaults = self.config().rate_limits;
+
+		let limits = match guild_id {
+			Some(guild_id) => Tables::Guilds
+				.get_entry::<GuildSettings>(self.database(), &guild_id)
+				.await
+				.map_or(defaults, |settings| settings.rate_limits(defaults)),
+			None => defaults,
+		};
+
+		self.rate_limiter()
+			.check(user_id, guild_id, limits, Instant::now())
+	}
+
+	// playground commands call this once their input is known to be valid, so bad input doesn't
+	// cost a request
+	pub async fn respond_if_rate_limited(&self, data: &mut SlashData) -> Result<bool, HttpError> {
+		match self
+			.check_rate_limit(data.user_id(), data.command.guild_id)
+			.await
+		{
+			Ok(()) => Ok(false),
+			Err(retry_after) => {
+				data.message(retry_message(retry_after)).ephemeral();
+				self.respond(data).await?;
+
+				Ok(true)
+			}
 		}
 	}
 
-	pub async fn ack(self, data: &SlashData) -> Result<(), HttpError> {
+	pub async fn ack(&self, data: &SlashData) -> Result<(), HttpError> {
 		self.context()
 			.interaction_client()
 			.create_response(
 				data.command.id,
 				&data.command.token,
-				&InteractionResponse::DeferredChannelMessageWithSource(SlashData::BASE),
+				&InteractionResponse::DeferredChannelMessageWithSource(CallbackData {
+					flags: data.callback.flags,
+					..SlashData::BASE
+				}),
 			)
 			.exec()
 			.await?;
@@ -118,7 +253,7 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
-	pub async fn respond(self, data: &mut SlashData) -> Result<(), HttpError> {
+	pub async fn respond(&self, data: &mut SlashData) -> Result<(), HttpError> {
 		self.context()
 			.interaction_client()
 			.create_response(
@@ -135,15 +270,32 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
-	pub async fn update(self, data: &mut SlashData) -> Result<()> {
+	pub async fn update(&self, data: &mut SlashData) -> Result<()> {
 		let callback_data = mem::replace(&mut data.callback, SlashData::BASE);
+		let attachments = mem::take(&mut data.attachments);
+
+		self.update_original(&data.command.token, &callback_data, &attachments)
+			.await
+	}
+
+	pub async fn update_original(
+		&self,
+		token: &str,
+		callback_data: &CallbackData,
+		attachments: &[(String, Vec<u8>)],
+	) -> Result<()> {
 		let context = self.interaction_client();
-		let update_interaction = context.update_response(&data.command.token);
+		let update_interaction = context.update_response(token);
 
-		let bytes = serde_json::to_vec(&callback_data).into_diagnostic()?;
+		let bytes = serde_json::to_vec(callback_data).into_diagnostic()?;
+		let files = attachments
+			.iter()
+			.map(|(filename, content)| AttachmentFile::from_bytes(filename, content))
+			.collect::<Vec<_>>();
 
 		update_interaction
 			.payload_json(&bytes[..])
+			.attachments(&files)
 			.exec()
 			.await
 			.into_diagnostic()?;
@@ -151,7 +303,30 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
-	pub async fn autocomplete(self, data: &mut SlashData) -> Result<(), HttpError> {
+	pub async fn delete_original(&self, token: &str) -> Result<(), HttpError> {
+		self.interaction_client()
+			.delete_response(token)
+			.exec()
+			.await?;
+
+		Ok(())
+	}
+
+	pub async fn respond_component(
+		&self,
+		component: &MessageComponentInteraction,
+		response: &InteractionResponse,
+	) -> Result<(), HttpError> {
+		self.context()
+			.interaction_client()
+			.create_response(component.id, &component.token, response)
+			.exec()
+			.await?;
+
+		Ok(())
+	}
+
+	pub async fn autocomplete(&self, data: &mut SlashData) -> Result<(), HttpError> {
 		let autocomplete_data =
 			mem::replace(&mut data.autocomplete, Autocomplete { choices: vec![] });
 		let context = self.context();
@@ -168,29 +343,65 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
-	pub async fn raw_get(self, data: &SlashData) -> Result<Message> {
+	pub async fn raw_get(&self, data: &SlashData) -> Result<Message> {
 		let http = self.interaction_client();
 		let get_original = http.response(&data.command.token);
 
 		model!(get_original).await.into_diagnostic()
 	}
+}
+
+// the names have to match the ones each command gives discord in `define`
+macro_rules! commands {
+	($($name:literal => $command:ty),* $(,)?) => {
+		impl InteractionsHelper {
+			pub const COMMAND_NAMES: &'static [&'static str] = &[$($name),*];
 
-	fn match_command(name: &str, data: CommandData) -> Option<Box<dyn SlashCommand>> {
-		match name {
-			"ping" => Some(Box::new(Ping {})),
-			"crate" => Some(Box::new(Crate::parse(data).unwrap())),
-			"tag" => Some(Box::new(Tag::parse(data).unwrap())),
-			_ => None,
+			fn match_command(
+				name: &str,
+				data: CommandData,
+			) -> Option<Result<Box<dyn SlashCommand>>> {
+				match name {
+					$($name => Some(
+						<$command>::parse(data).map(|command| Box::new(command) as Box<dyn SlashCommand>)
+					),)*
+					_ => None,
+				}
+			}
+
+			#[must_use]
+			pub const fn required_intents() -> Intents {
+				Intents::empty()$(.union(<$command>::INTENTS))*
+			}
+
+			fn get_slashies() -> Vec<Command> {
+				vec![$(<$command>::define().build()),*]
+			}
 		}
-	}
+	};
+}
 
-	fn get_slashies() -> [Command; 3] {
-		[Ping::define(), Crate::define(), Tag::define()].map(CommandBuilder::build)
-	}
+commands! {
+	"ping" => Ping,
+	"status" => Status,
+	"crate" => Crate,
+	"tag" => Tag,
+	"play" => Play,
+	"miri" => Miri,
+	"expand" => Expand,
+	"fmt" => Fmt,
+	"Format code" => FormatCode,
+	"clippy" => Clippy,
+	"eval" => Eval,
+	"Run code" => RunCode,
+	"asm" => Asm,
+	"test" => Test,
+	"explain" => Explain,
+	"settings" => Settings,
 }
 
 impl QuickAccess for InteractionsHelper {
-	fn context(&self) -> Context {
+	fn context(&self) -> &Context {
 		self.0.context()
 	}
 }
@@ -1,9 +1,11 @@
 use crate::state::{Context, QuickAccess};
 
 mod color;
+pub mod error_codes;
 mod interactions;
 pub mod parsing;
 pub mod playground;
+pub mod rate_limit;
 
 pub use self::{color::Color, interactions::InteractionsHelper};
 
@@ -13,7 +15,7 @@ pub const STARLIGHT_COLORS: [Color; 3] = [
 	Color::new(183, 47, 0),
 ];
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[must_use = "Helpers do nothing if not used"]
 pub struct Helpers {
 	context: Context,
@@ -30,7 +32,7 @@ impl Helpers {
 }
 
 impl QuickAccess for Helpers {
-	fn context(&self) -> Context {
-		self.context
+	fn context(&self) -> &Context {
+		&self.context
 	}
 }
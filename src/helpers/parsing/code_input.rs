@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use reqwest::Url;
+use twilight_model::channel::Message;
+
+use super::{CodeBlock, CodeBlockError};
+use crate::prelude::*;
+
+const ATTACHMENT_HOSTS: [&str; 2] = ["cdn.discordapp.com", "media.discordapp.net"];
+const MAX_ATTACHMENT_SIZE: usize = 64 * 1024;
+
+fn is_rust_file(path: &str) -> bool {
+	Path::new(path)
+		.extension()
+		.map_or(false, |extension| extension.eq_ignore_ascii_case("rs"))
+}
+
+#[derive(Debug, Error)]
+pub enum CodeInputError {
+	#[error("no code was given")]
+	Missing,
+	#[error("the file isn't a discord attachment")]
+	InvalidUrl,
+	#[error("only `.rs` files can be used as code")]
+	NotRust,
+	#[error("the file is larger than the {} KiB limit", MAX_ATTACHMENT_SIZE / 1024)]
+	TooLarge,
+	#[error("the file isn't valid UTF-8")]
+	InvalidUtf8,
+	#[error("failed to download the file")]
+	Download(#[from] reqwest::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeInput {
+	Inline(String),
+	Attachment(String),
+	Missing,
+}
+
+impl CodeInput {
+	#[must_use]
+	pub fn new(code: Option<String>) -> Self {
+		code.map_or(Self::Missing, |code| Self::Inline(CodeBlock::strip(&code)))
+	}
+
+	pub fn from_message(message: &Message) -> Result<Self, CodeBlockError> {
+		let attachment = message
+			.attachments
+			.iter()
+			.find(|attachment| is_rust_file(&attachment.filename))
+			.map(|attachment| attachment.url.as_str());
+
+		Self::from_content(&message.content, attachment)
+	}
+
+	// a fenced block wins over an attached file, which wins over inline code
+	fn from_content(content: &str, attachment: Option<&str>) -> Result<Self, CodeBlockError> {
+		if let Some(code_block) = CodeBlock::find_fenced(content) {
+			return Ok(Self::Inline(code_block.code));
+		}
+
+		if let Some(url) = attachment {
+			return Ok(Self::Attachment(url.to_owned()));
+		}
+
+		CodeBlock::find(content).map(|code_block| Self::Inline(code_block.code))
+	}
+
+	pub async fn resolve(&self, client: &reqwest::Client) -> Result<String, CodeInputError> {
+		let url = match self {
+			Self::Inline(code) => return Ok(code.clone()),
+			Self::Attachment(url) => Self::validate_url(url)?,
+			Self::Missing => return Err(CodeInputError::Missing),
+		};
+
+		let mut response = client.get(url).send().await?.error_for_status()?;
+
+		if response
+			.content_length()
+			.map_or(false, |length| length > MAX_ATTACHMENT_SIZE as u64)
+		{
+			return Err(CodeInputError::TooLarge);
+		}
+
+		// the content length isn't always sent, so the limit is checked while reading as well
+		let mut content = Vec::new();
+
+		while let Some(chunk) = response.chunk().await? {
+			content.extend_from_slice(&chunk);
+
+			if content.len() > MAX_ATTACHMENT_SIZE {
+				return Err(CodeInputError::TooLarge);
+			}
+		}
+
+		String::from_utf8(content).map_err(|_| CodeInputError::InvalidUtf8)
+	}
+
+	fn validate_url(url: &str) -> Result<Url, CodeInputError> {
+		let url = Url::parse(url.trim()).map_err(|_| CodeInputError::InvalidUrl)?;
+
+		if url.scheme() != "https"
+			|| !url
+				.host_str()
+				.map_or(false, |host| ATTACHMENT_HOSTS.contains(&host))
+		{
+			return Err(CodeInputError::InvalidUrl);
+		}
+
+		if !is_rust_file(url.path()) {
+			return Err(CodeInputError::NotRust);
+		}
+
+		Ok(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{CodeInput, CodeInputError};
+
+	#[test]
+	fn validate_url() {
+		assert!(CodeInput::validate_url(
+			"https://cdn.discordapp.com/attachments/1/2/main.rs?ex=abc"
+		)
+		.is_ok());
+		assert!(matches!(
+			CodeInput::validate_url("https://example.com/main.rs"),
+			Err(CodeInputError::InvalidUrl)
+		));
+		assert!(matches!(
+			CodeInput::validate_url("http://cdn.discordapp.com/attachments/1/2/main.rs"),
+			Err(CodeInputError::InvalidUrl)
+		));
+		assert!(matches!(
+			CodeInput::validate_url("https://cdn.discordapp.com/attachments/1/2/image.png"),
+			Err(CodeInputError::NotRust)
+		));
+	}
+
+	#[test]
+	fn new_strips_code_block() {
+		assert_eq!(
+			CodeInput::new(Some("```rust\nfn main() {}\n```".to_owned())),
+			CodeInput::Inline("fn main() {}".to_owned())
+		);
+		assert_eq!(CodeInput::new(None), CodeInput::Missing);
+	}
+
+	#[test]
+	fn from_message_content() {
+		let attachment = Some("https://cdn.discordapp.com/attachments/1/2/main.rs");
+		let content =
+			"`cargo fmt` mangles this, any idea why?\n```rust\nfn main(){println!(\"hi\")}\n```";
+
+		assert_eq!(
+			CodeInput::from_content(content, attachment).unwrap(),
+			CodeInput::Inline("fn main(){println!(\"hi\")}".to_owned())
+		);
+		assert_eq!(
+			CodeInput::from_content("see `main.rs`", attachment).unwrap(),
+			CodeInput::Attachment(attachment.unwrap().to_owned())
+		);
+		assert_eq!(
+			CodeInput::from_content("what does `1 << 3` do?", None).unwrap(),
+			CodeInput::Inline("1 << 3".to_owned())
+		);
+	}
+}
@@ -17,6 +17,42 @@ pub struct CodeBlock {
 	pub rest: String,
 }
 
+impl CodeBlock {
+	#[must_use]
+	pub fn new(code: &str, language: Option<&str>) -> Self {
+		Self {
+			code: code.to_owned(),
+			language: language.map(ToOwned::to_owned),
+			rest: String::new(),
+		}
+	}
+
+	pub fn find(input: &str) -> Result<Self, CodeBlockError> {
+		if let Some(code_block) = Self::find_fenced(input) {
+			return Ok(code_block);
+		}
+
+		input
+			.find('`')
+			.map_or(Err(CodeBlockError::Missing), |start| input[start..].parse())
+	}
+
+	#[must_use]
+	pub fn find_fenced(input: &str) -> Option<Self> {
+		input
+			.find("```")
+			.and_then(|start| input[start..].parse().ok())
+	}
+
+	#[must_use]
+	pub fn strip(input: &str) -> String {
+		input
+			.trim()
+			.parse::<Self>()
+			.map_or_else(|_| input.trim().to_owned(), |code_block| code_block.code)
+	}
+}
+
 impl Display for CodeBlock {
 	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
 		f.write_str("```")?;
@@ -40,8 +76,10 @@ impl FromStr for CodeBlock {
 
 			let mut language = None;
 			if let Some(first_newline) = code_block.find('\n') {
-				if !code_block[..first_newline].contains(char::is_whitespace) {
-					language = Some(&code_block[..first_newline]);
+				let first_line = &code_block[..first_newline];
+
+				if !first_line.is_empty() && !first_line.contains(char::is_whitespace) {
+					language = Some(first_line);
 					code_block = &code_block[(first_newline + 1)..];
 				}
 			}
@@ -99,4 +137,41 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn find_prefers_fenced_block() {
+		let code_block =
+			CodeBlock::find("use `foo` like this:\n```rust\nfn main() {}\n```").unwrap();
+
+		assert_eq!(code_block.code, "fn main() {}");
+		assert_eq!(code_block.language.as_deref(), Some("rust"));
+	}
+
+	#[test]
+	fn find_ignores_stray_backtick() {
+		let code_block = CodeBlock::find("it's ` broken:\n```\nfn main() {}\n```").unwrap();
+
+		assert_eq!(code_block.code, "fn main() {}");
+		assert_eq!(code_block.language, None);
+	}
+
+	#[test]
+	fn find_inline_and_missing() {
+		assert_eq!(CodeBlock::find("run `1 + 1` please").unwrap().code, "1 + 1");
+		assert!(matches!(
+			CodeBlock::find("no code here"),
+			Err(CodeBlockError::Missing)
+		));
+		assert!(matches!(
+			CodeBlock::find("just a ` backtick"),
+			Err(CodeBlockError::Malformed)
+		));
+	}
+
+	#[test]
+	fn strip() {
+		assert_eq!(CodeBlock::strip("```rs\nlet x = 1;\n```"), "let x = 1;");
+		assert_eq!(CodeBlock::strip("`let x = 1;`"), "let x = 1;");
+		assert_eq!(CodeBlock::strip("  let x = 1;  "), "let x = 1;");
+	}
 }
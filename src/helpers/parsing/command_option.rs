@@ -1,4 +1,7 @@
-use twilight_model::application::interaction::application_command::CommandOptionValue;
+use twilight_model::{
+	application::interaction::application_command::{CommandData, CommandOptionValue},
+	channel::Message,
+};
 
 pub trait CommandParse<T> {
 	// to avoid naming conflicts with anything else that could use parse..
@@ -34,3 +37,7 @@ impl CommandParse<bool> for CommandOptionValue {
 		}
 	}
 }
+
+pub fn target_message(data: CommandData) -> Option<Message> {
+	data.resolved?.messages.into_values().next()
+}
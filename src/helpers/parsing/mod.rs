@@ -1,7 +1,9 @@
+mod code_input;
 mod codeblock;
 mod command_option;
 
 pub use self::{
+	code_input::{CodeInput, CodeInputError},
 	codeblock::{CodeBlock, CodeBlockError},
-	command_option::CommandParse,
+	command_option::{target_message, CommandParse},
 };
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+use super::{truncate, DiagnosticLevel};
+use crate::helpers::parsing::CodeBlock;
+
+// discord only renders the basic 8 colors, along with bold and underline
+const RESET: &str = "\u{1b}[0m";
+const BOLD: &str = "\u{1b}[1m";
+const BOLD_BLUE: &str = "\u{1b}[1;34m";
+
+const fn level_color(level: DiagnosticLevel) -> &'static str {
+	match level {
+		DiagnosticLevel::Error => "\u{1b}[1;31m",
+		DiagnosticLevel::Warning => "\u{1b}[1;33m",
+		DiagnosticLevel::Note => "\u{1b}[1;32m",
+		DiagnosticLevel::Help => "\u{1b}[1;36m",
+	}
+}
+
+#[must_use]
+pub fn colorize(output: &str) -> String {
+	let mut level = DiagnosticLevel::Error;
+
+	output
+		.lines()
+		.map(|line| {
+			if let Some((line_level, rest)) = DiagnosticLevel::strip_prefix(line) {
+				if let Some((label, message)) = split_header(rest) {
+					level = line_level;
+
+					return format!(
+						"{}{}{}{}{}{}{}",
+						level_color(level),
+						&line[..line.len() - rest.len()],
+						label,
+						RESET,
+						BOLD,
+						message,
+						RESET
+					);
+				}
+			}
+
+			colorize_gutter(line, level).unwrap_or_else(|| line.to_owned())
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+#[must_use]
+pub fn code_block(output: &str, ansi: bool) -> CodeBlock {
+	if ansi {
+		CodeBlock::new(&colorize(output), Some("ansi"))
+	} else {
+		CodeBlock::new(output, None)
+	}
+}
+
+// colorizes before cutting the output down, so the escape codes count towards `max`
+#[must_use]
+pub fn preview(output: &str, max: usize, ansi: bool) -> CodeBlock {
+	let output = if ansi {
+		Cow::Owned(colorize(output))
+	} else {
+		Cow::Borrowed(output)
+	};
+	let language = ansi.then(|| "ansi");
+
+	let mut preview = truncate(&output, max);
+
+	if preview.len() == output.len() {
+		return CodeBlock::new(preview, language);
+	}
+
+	if let Some(start) = preview.rfind('\u{1b}') {
+		if !preview[start..].contains('m') {
+			preview = &preview[..start];
+		}
+	}
+
+	if ansi {
+		CodeBlock::new(&format!("{}{}\n...", preview, RESET), language)
+	} else {
+		CodeBlock::new(&format!("{}\n...", preview), language)
+	}
+}
+
+// splits `[E0382]: message` into `[E0382]:` and ` message`
+fn split_header(rest: &str) -> Option<(&str, &str)> {
+	if !(rest.starts_with(':') || rest.starts_with('[')) {
+		return None;
+	}
+
+	let end = rest.find(": ")? + 1;
+
+	Some(rest.split_at(end))
+}
+
+fn colorize_gutter(line: &str, level: DiagnosticLevel) -> Option<String> {
+	let trimmed = line.trim_start();
+	let indent = &line[..line.len() - trimmed.len()];
+
+	if let Some(location) = trimmed.strip_prefix("-->") {
+		return Some(format!("{}{}-->{}{}", indent, BOLD_BLUE, RESET, location));
+	}
+
+	if let Some(child) = trimmed.strip_prefix('=') {
+		let child = child.trim_start();
+		let (child_level, rest) = DiagnosticLevel::strip_prefix(child)?;
+		let (label, message) = split_header(rest)?;
+
+		return Some(format!(
+			"{}{}={} {}{}{}{}{}",
+			indent,
+			BOLD_BLUE,
+			RESET,
+			level_color(child_level),
+			&child[..child.len() - rest.len()],
+			label,
+			RESET,
+			message
+		));
+	}
+
+	let (gutter, content) = line.split_once('|')?;
+
+	if !gutter
+		.chars()
+		.all(|c| c.is_ascii_digit() || c.is_whitespace())
+	{
+		return None;
+	}
+
+	if gutter.trim().is_empty() {
+		Some(format!(
+			"{}{}|{}{}{}",
+			BOLD_BLUE,
+			gutter,
+			level_color(level),
+			content,
+			RESET
+		))
+	} else {
+		Some(format!("{}{}|{}{}", BOLD_BLUE, gutter, RESET, content))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{colorize, preview};
+
+	#[test]
+	fn colorize_diagnostic() {
+		let output = "error[E0382]: borrow of moved value: `x`\n --> src/main.rs:4:20\n4 |     println!(\"{}\", x);\n  |                    ^ value borrowed here after move\n  = note: some note";
+
+		assert_eq!(
+			colorize(output),
+			"\u{1b}[1;31merror[E0382]:\u{1b}[0m\u{1b}[1m borrow of moved value: `x`\u{1b}[0m\n \u{1b}[1;34m-->\u{1b}[0m src/main.rs:4:20\n\u{1b}[1;34m4 |\u{1b}[0m     println!(\"{}\", x);\n\u{1b}[1;34m  |\u{1b}[1;31m                    ^ value borrowed here after move\u{1b}[0m\n  \u{1b}[1;34m=\u{1b}[0m \u{1b}[1;32mnote:\u{1b}[0m some note"
+		);
+	}
+
+	#[test]
+	fn preview_counts_escape_codes() {
+		let output =
+			"error[E0382]: borrow of moved value: `x`\n --> src/main.rs:4:20\n".repeat(100);
+
+		let preview = preview(&output, 1500, true).to_string();
+
+		assert!(preview.len() <= 1500 + "```ansi\n\u{1b}[0m\n...\n```".len());
+		assert!(preview.ends_with("\u{1b}[0m\n...\n```"));
+
+		let body = preview.trim_end_matches("\u{1b}[0m\n...\n```");
+		let last_escape = body.rfind('\u{1b}').unwrap();
+		let (code, _) = body[last_escape + 2..].split_once('m').unwrap();
+		assert!(code.chars().all(|c| c.is_ascii_digit() || c == ';'));
+	}
+}
@@ -0,0 +1,187 @@
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+use super::{BuildMode, CrateType, Edition, PlaygroundRequest, PlaygroundResponse, RustChannel};
+
+const MAX_ENTRIES: usize = 256;
+const TIME_TO_LIVE: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlaygroundCacheKey {
+	code: String,
+	channel: RustChannel,
+	edition: Edition,
+	crate_type: CrateType,
+	mode: BuildMode,
+	tests: bool,
+}
+
+impl From<&PlaygroundRequest<'_>> for PlaygroundCacheKey {
+	fn from(request: &PlaygroundRequest<'_>) -> Self {
+		Self {
+			code: request.code.to_owned(),
+			channel: request.channel,
+			edition: request.edition,
+			crate_type: request.crate_type,
+			mode: request.mode,
+			tests: request.tests,
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct PlaygroundCache {
+	entries: Mutex<HashMap<PlaygroundCacheKey, (Instant, PlaygroundResponse)>>,
+	max_entries: usize,
+	time_to_live: Duration,
+}
+
+impl PlaygroundCache {
+	#[must_use]
+	pub fn new(max_entries: usize, time_to_live: Duration) -> Self {
+		Self {
+			entries: Mutex::default(),
+			max_entries,
+			time_to_live,
+		}
+	}
+
+	pub fn get(&self, request: &PlaygroundRequest<'_>, now: Instant) -> Option<PlaygroundResponse> {
+		let mut entries = self.entries.lock().unwrap();
+		let key = PlaygroundCacheKey::from(request);
+
+		let (inserted, response) = entries.get(&key)?;
+
+		if now.saturating_duration_since(*inserted) < self.time_to_live {
+			return Some(PlaygroundResponse {
+				cached: true,
+				..response.clone()
+			});
+		}
+
+		entries.remove(&key);
+
+		None
+	}
+
+	pub fn insert(
+		&self,
+		request: &PlaygroundRequest<'_>,
+		response: &PlaygroundResponse,
+		now: Instant,
+	) {
+		// failures include timeouts and playground errors, which are worth running again
+		if !response.success {
+			return;
+		}
+
+		let mut entries = self.entries.lock().unwrap();
+
+		entries.retain(|_, (inserted, _)| {
+			now.saturating_duration_since(*inserted) < self.time_to_live
+		});
+
+		if entries.len() >= self.max_entries {
+			let oldest = entries
+				.iter()
+				.min_by_key(|(_, (inserted, _))| *inserted)
+				.map(|(key, _)| key.clone());
+
+			if let Some(oldest) = oldest {
+				entries.remove(&oldest);
+			}
+		}
+
+		entries.insert(PlaygroundCacheKey::from(request), (now, response.clone()));
+	}
+}
+
+impl Default for PlaygroundCache {
+	fn default() -> Self {
+		Self::new(MAX_ENTRIES, TIME_TO_LIVE)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use super::PlaygroundCache;
+	use crate::helpers::playground::{
+		BuildMode, Edition, PlaygroundRequest, PlaygroundResponse, RustChannel,
+	};
+
+	fn request(code: &str) -> PlaygroundRequest<'_> {
+		PlaygroundRequest::new(
+			code,
+			RustChannel::Stable,
+			Edition::E2021,
+			BuildMode::Debug,
+			false,
+		)
+	}
+
+	fn response(stdout: &str) -> PlaygroundResponse {
+		PlaygroundResponse {
+			success: true,
+			stdout: stdout.to_owned(),
+			stderr: String::new(),
+			program_stderr: String::new(),
+			cached: false,
+		}
+	}
+
+	#[test]
+	fn hit_and_expire() {
+		let cache = PlaygroundCache::new(4, Duration::from_secs(60));
+		let now = Instant::now();
+
+		cache.insert(&request("fn main() {}"), &response("a"), now);
+
+		let hit = cache.get(&request("fn main() {}"), now + Duration::from_secs(30));
+		assert!(hit
+			.as_ref()
+			.map_or(false, |hit| hit.cached && hit.stdout == "a"));
+
+		let other_channel = PlaygroundRequest {
+			channel: RustChannel::Nightly,
+			..request("fn main() {}")
+		};
+		assert!(cache.get(&other_channel, now).is_none());
+
+		assert!(cache
+			.get(&request("fn main() {}"), now + Duration::from_secs(60))
+			.is_none());
+	}
+
+	#[test]
+	fn evicts_oldest() {
+		let cache = PlaygroundCache::new(2, Duration::from_secs(60));
+		let now = Instant::now();
+
+		cache.insert(&request("a"), &response("a"), now);
+		cache.insert(&request("b"), &response("b"), now + Duration::from_secs(1));
+		cache.insert(&request("c"), &response("c"), now + Duration::from_secs(2));
+
+		assert!(cache.get(&request("a"), now).is_none());
+		assert!(cache.get(&request("b"), now).is_some());
+		assert!(cache.get(&request("c"), now).is_some());
+	}
+
+	#[test]
+	fn skips_failures() {
+		let cache = PlaygroundCache::new(4, Duration::from_secs(60));
+		let now = Instant::now();
+
+		let failure = PlaygroundResponse {
+			success: false,
+			..response("timed out")
+		};
+		cache.insert(&request("fn main() {}"), &failure, now);
+
+		assert!(cache.get(&request("fn main() {}"), now).is_none());
+	}
+}
@@ -0,0 +1,192 @@
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+	time::{Duration, Instant},
+};
+
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+use super::{
+	ClippyRequest, ClippyResponse, CompileResponse, FormatRequest, FormatResponse,
+	MacroExpansionRequest, MiriRequest, PlaygroundCache, PlaygroundCompileRequest,
+	PlaygroundRequest, PlaygroundResponse, PlaygroundSessions,
+};
+use crate::{prelude::*, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct PlaygroundEndpoint {
+	http: Client,
+	base_url: String,
+}
+
+impl PlaygroundEndpoint {
+	fn new(base_url: &str, timeout: Duration, user_agent: &str) -> Result<Self> {
+		let http = Client::builder()
+			.timeout(timeout)
+			.user_agent(user_agent)
+			.build()
+			.into_diagnostic()?;
+
+		Ok(Self {
+			http,
+			base_url: base_url.trim_end_matches('/').to_owned(),
+		})
+	}
+
+	pub fn from_config(config: &Config) -> Result<Self> {
+		Self::new(
+			&config.playground_url,
+			config.playground_timeout,
+			&config.playground_user_agent,
+		)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaygroundClient {
+	endpoint: Arc<RwLock<PlaygroundEndpoint>>,
+	sessions: Arc<PlaygroundSessions>,
+	cache: Arc<PlaygroundCache>,
+}
+
+impl PlaygroundClient {
+	pub fn new(base_url: &str, timeout: Duration, user_agent: &str) -> Result<Self> {
+		Ok(Self {
+			endpoint: Arc::new(RwLock::new(PlaygroundEndpoint::new(
+				base_url, timeout, user_agent,
+			)?)),
+			sessions: Arc::default(),
+			cache: Arc::default(),
+		})
+	}
+
+	pub fn from_config(config: &Config) -> Result<Self> {
+		Self::new(
+			&config.playground_url,
+			config.playground_timeout,
+			&config.playground_user_agent,
+		)
+	}
+
+	pub fn reconfigure(&self, endpoint: PlaygroundEndpoint) {
+		*self.endpoint.write().unwrap() = endpoint;
+	}
+
+	#[must_use]
+	pub fn base_url(&self) -> String {
+		self.endpoint().base_url
+	}
+
+	#[must_use]
+	pub fn sessions(&self) -> &PlaygroundSessions {
+		&*self.sessions
+	}
+
+	pub async fn execute(&self, request: &PlaygroundRequest<'_>) -> Result<PlaygroundResponse> {
+		if let Some(response) = self.cache.get(request, Instant::now()) {
+			return Ok(response);
+		}
+
+		self.execute_uncached(request).await
+	}
+
+	// skips the cache lookup, a fresh success still replaces whatever was cached
+	pub async fn execute_uncached(
+		&self,
+		request: &PlaygroundRequest<'_>,
+	) -> Result<PlaygroundResponse> {
+		let response: PlaygroundResponse = self.post("execute", request).await?;
+
+		self.cache.insert(request, &response, Instant::now());
+
+		Ok(response)
+	}
+
+	pub async fn compile(&self, request: &PlaygroundCompileRequest<'_>) -> Result<CompileResponse> {
+		self.post("compile", request).await
+	}
+
+	pub async fn miri(&self, request: &MiriRequest<'_>) -> Result<PlaygroundResponse> {
+		self.post("miri", request).await
+	}
+
+	pub async fn macro_expansion(
+		&self,
+		request: &MacroExpansionRequest<'_>,
+	) -> Result<PlaygroundResponse> {
+		self.post("macro-expansion", request).await
+	}
+
+	pub async fn format(&self, request: &FormatRequest<'_>) -> Result<FormatResponse> {
+		self.post("format", request).await
+	}
+
+	pub async fn clippy(&self, request: &ClippyRequest<'_>) -> Result<ClippyResponse> {
+		self.post("clippy", request).await
+	}
+
+	pub async fn gist(&self, code: &str) -> Result<String> {
+		let mut payload = HashMap::new();
+		payload.insert("code", code);
+
+		let mut resp: HashMap<String, String> = self.post("meta/gist", &payload).await?;
+
+		event!(Level::INFO, "gist response: {:?}", resp);
+
+		resp.remove("id").ok_or_else(|| error!("no gist found"))
+	}
+
+	pub async fn fetch_gist(&self, id: &str) -> Result<String> {
+		let endpoint = self.endpoint();
+
+		let mut resp: HashMap<String, String> = endpoint
+			.http
+			.get(format!("{}/meta/gist/{}", endpoint.base_url, id))
+			.send()
+			.await
+			.into_diagnostic()?
+			.error_for_status()
+			.into_diagnostic()?
+			.json()
+			.await
+			.into_diagnostic()?;
+
+		resp.remove("code")
+			.ok_or_else(|| error!("no code found in gist"))
+	}
+
+	pub async fn share(&self, request: &PlaygroundRequest<'_>) -> Result<String> {
+		let gist = self.gist(request.code).await?;
+
+		Ok(format!(
+			"{}/?version={}&mode={}&edition={}&gist={}",
+			self.base_url(),
+			request.channel,
+			request.mode,
+			request.edition,
+			gist
+		))
+	}
+
+	async fn post<T, R>(&self, endpoint: &str, body: &T) -> Result<R>
+	where
+		T: Serialize + ?Sized + Sync,
+		R: DeserializeOwned,
+	{
+		let PlaygroundEndpoint { http, base_url } = self.endpoint();
+
+		http.post(format!("{}/{}", base_url, endpoint))
+			.json(body)
+			.send()
+			.await
+			.into_diagnostic()?
+			.json()
+			.await
+			.into_diagnostic()
+	}
+
+	fn endpoint(&self) -> PlaygroundEndpoint {
+		self.endpoint.read().unwrap().clone()
+	}
+}
@@ -0,0 +1,156 @@
+use futures_util::future::join_all;
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+use twilight_model::channel::embed::Embed;
+
+use super::{ansi, PlaygroundClient, PlaygroundRequest, PlaygroundResponse, RustChannel};
+use crate::{helpers::STARLIGHT_COLORS, prelude::*};
+
+// embed field values are capped at 1024 characters, this leaves room for the status and fences
+const FIELD_OUTPUT_LENGTH: usize = 960;
+
+#[derive(Debug, Clone)]
+pub struct PlaygroundComparison {
+	pub results: Vec<(Vec<RustChannel>, PlaygroundResponse)>,
+}
+
+impl PlaygroundComparison {
+	pub const CHANNELS: [RustChannel; 3] =
+		[RustChannel::Stable, RustChannel::Beta, RustChannel::Nightly];
+
+	pub async fn run(
+		client: &PlaygroundClient,
+		request: PlaygroundRequest<'_>,
+		show_compiler_warnings: bool,
+		fresh: bool,
+	) -> Result<Self> {
+		let responses = join_all(Self::CHANNELS.map(|channel| async move {
+			let request = PlaygroundRequest { channel, ..request };
+
+			let mut response = if fresh {
+				client.execute_uncached(&request).await?
+			} else {
+				client.execute(&request).await?
+			};
+
+			response.format(show_compiler_warnings);
+
+			Ok::<_, miette::Report>((channel, response))
+		}))
+		.await
+		.into_iter()
+		.collect::<Result<Vec<_>>>()?;
+
+		Ok(Self::new(responses))
+	}
+
+	#[must_use]
+	pub fn new(responses: impl IntoIterator<Item = (RustChannel, PlaygroundResponse)>) -> Self {
+		let mut results: Vec<(Vec<RustChannel>, PlaygroundResponse)> = Vec::new();
+
+		for (channel, response) in responses {
+			let existing = results.iter_mut().find(|(_, existing)| {
+				existing.success == response.success
+					&& existing.stdout.trim() == response.stdout.trim()
+					&& existing.stderr.trim() == response.stderr.trim()
+			});
+
+			if let Some((channels, _)) = existing {
+				channels.push(channel);
+			} else {
+				results.push((vec![channel], response));
+			}
+		}
+
+		Self { results }
+	}
+
+	#[must_use]
+	pub fn is_identical(&self) -> bool {
+		self.results.len() == 1
+	}
+
+	pub fn embed(&self, ansi: bool) -> Result<Embed> {
+		let mut embed_builder = EmbedBuilder::new()
+			.color(STARLIGHT_COLORS[0].to_decimal())
+			.title("Channel comparison");
+
+		if self.is_identical() {
+			embed_builder = embed_builder.description("all channels produced the same output");
+		}
+
+		if self.results.iter().any(|(_, response)| response.cached) {
+			embed_builder = embed_builder.footer(EmbedFooterBuilder::new(
+				"some results were cached, use `fresh` to run them again",
+			));
+		}
+
+		for (channels, response) in &self.results {
+			let name = channels
+				.iter()
+				.map(ToString::to_string)
+				.collect::<Vec<_>>()
+				.join(", ");
+
+			embed_builder =
+				embed_builder.field(EmbedFieldBuilder::new(name, field_value(response, ansi)));
+		}
+
+		embed_builder.build().into_diagnostic()
+	}
+}
+
+fn field_value(response: &PlaygroundResponse, ansi: bool) -> String {
+	let status = if response.success {
+		"✅ success"
+	} else {
+		"❌ failure"
+	};
+
+	let output = [response.stderr.trim(), response.stdout.trim()]
+		.into_iter()
+		.filter(|output| !output.is_empty())
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	if output.is_empty() {
+		return format!("{}\nno output", status);
+	}
+
+	format!(
+		"{}\n{}",
+		status,
+		ansi::preview(&output, FIELD_OUTPUT_LENGTH, ansi)
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{PlaygroundComparison, PlaygroundResponse, RustChannel};
+
+	fn response(success: bool, stdout: &str) -> PlaygroundResponse {
+		PlaygroundResponse {
+			success,
+			stdout: stdout.to_owned(),
+			stderr: String::new(),
+			program_stderr: String::new(),
+			cached: false,
+		}
+	}
+
+	#[test]
+	fn collapse_identical() {
+		let comparison = PlaygroundComparison::new([
+			(RustChannel::Stable, response(true, "hello\n")),
+			(RustChannel::Beta, response(true, "hello")),
+			(RustChannel::Nightly, response(false, "hello")),
+		]);
+
+		assert_eq!(comparison.results.len(), 2);
+		assert_eq!(
+			comparison.results[0].0,
+			[RustChannel::Stable, RustChannel::Beta]
+		);
+		assert_eq!(comparison.results[1].0, [RustChannel::Nightly]);
+		assert!(!comparison.is_identical());
+	}
+}
@@ -0,0 +1,319 @@
+use twilight_embed_builder::EmbedBuilder;
+use twilight_model::channel::embed::Embed;
+
+use super::add_embed_fields;
+use crate::{helpers::STARLIGHT_COLORS, prelude::*};
+
+// summary lines printed by rustc and cargo after the actual diagnostics
+const SUMMARY_PREFIXES: [&str; 3] = ["aborting due to", "could not compile", "`playground` ("];
+const SUMMARY_SUFFIXES: [&str; 2] = ["warning emitted", "warnings emitted"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel {
+	Error,
+	Warning,
+	Note,
+	Help,
+}
+
+impl DiagnosticLevel {
+	pub(super) fn strip_prefix(line: &str) -> Option<(Self, &str)> {
+		[
+			(Self::Error, "error"),
+			(Self::Warning, "warning"),
+			(Self::Note, "note"),
+			(Self::Help, "help"),
+		]
+		.into_iter()
+		.find_map(|(level, prefix)| line.strip_prefix(prefix).map(|rest| (level, rest)))
+	}
+}
+
+impl Display for DiagnosticLevel {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		match self {
+			Self::Error => f.write_str("error"),
+			Self::Warning => f.write_str("warning"),
+			Self::Note => f.write_str("note"),
+			Self::Help => f.write_str("help"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticSpan {
+	pub file: String,
+	pub line: usize,
+	pub column: usize,
+	pub label: Option<String>,
+}
+
+impl DiagnosticSpan {
+	fn parse(location: &str) -> Option<Self> {
+		let mut parts = location.trim().rsplitn(3, ':');
+
+		let column = parts.next()?.parse().ok()?;
+		let line = parts.next()?.parse().ok()?;
+		let file = parts.next()?.to_owned();
+
+		Some(Self {
+			file,
+			line,
+			column,
+			label: None,
+		})
+	}
+}
+
+impl Display for DiagnosticSpan {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}:{}:{}", self.file, self.line, self.column)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+	pub level: DiagnosticLevel,
+	pub code: Option<String>,
+	pub message: String,
+	pub span: Option<DiagnosticSpan>,
+	pub children: Vec<(DiagnosticLevel, String)>,
+}
+
+impl Diagnostic {
+	#[must_use]
+	pub fn parse_all(stderr: &str) -> Vec<Self> {
+		let mut diagnostics = Vec::new();
+		let mut current: Option<Self> = None;
+		// labels are only taken from the primary snippet, not from the snippets of `help` suggestions
+		let mut in_primary = false;
+
+		for line in stderr.lines() {
+			if let Some(diagnostic) = Self::parse_header(line) {
+				diagnostics.extend(current.take());
+				current = diagnostic;
+				in_primary = true;
+				continue;
+			}
+
+			let diagnostic = match &mut current {
+				Some(diagnostic) => diagnostic,
+				None => continue,
+			};
+
+			let trimmed = line.trim_start();
+
+			if let Some((level @ (DiagnosticLevel::Note | DiagnosticLevel::Help), message)) =
+				DiagnosticLevel::strip_prefix(line)
+			{
+				if let Some(message) = message.strip_prefix(": ") {
+					diagnostic.children.push((level, message.to_owned()));
+					in_primary = false;
+				}
+			} else if let Some(child) = trimmed.strip_prefix("= ") {
+				if let Some((level, message)) = DiagnosticLevel::strip_prefix(child) {
+					if let Some(message) = message.strip_prefix(": ") {
+						diagnostic.children.push((level, message.to_owned()));
+					}
+				}
+			} else if let Some(location) = trimmed.strip_prefix("--> ") {
+				if diagnostic.span.is_none() {
+					diagnostic.span = DiagnosticSpan::parse(location);
+				}
+			} else if in_primary {
+				if let Some(span) = &mut diagnostic.span {
+					if span.label.is_none() {
+						span.label = Self::parse_label(line);
+					}
+				}
+			}
+		}
+
+		diagnostics.extend(current);
+
+		diagnostics
+	}
+
+	// `Some(None)` is a summary line, which ends the current diagnostic without starting a new one
+	#[allow(clippy::option_option)]
+	fn parse_header(line: &str) -> Option<Option<Self>> {
+		let (level, rest) = DiagnosticLevel::strip_prefix(line)?;
+
+		if !matches!(level, DiagnosticLevel::Error | DiagnosticLevel::Warning) {
+			return None;
+		}
+
+		let (code, rest) = match rest.strip_prefix('[') {
+			Some(rest) => {
+				let (code, rest) = rest.split_once(']')?;
+
+				(Some(code.to_owned()), rest)
+			}
+			None => (None, rest),
+		};
+
+		let message = rest.strip_prefix(": ")?;
+
+		if SUMMARY_PREFIXES
+			.iter()
+			.any(|prefix| message.starts_with(prefix))
+			|| SUMMARY_SUFFIXES
+				.iter()
+				.any(|suffix| message.ends_with(suffix))
+		{
+			return Some(None);
+		}
+
+		Some(Some(Self {
+			level,
+			code,
+			message: message.to_owned(),
+			span: None,
+			children: Vec::new(),
+		}))
+	}
+
+	fn parse_label(line: &str) -> Option<String> {
+		let (gutter, content) = line.split_once('|')?;
+
+		if !gutter.trim().is_empty() {
+			return None;
+		}
+
+		let label = content[content.find('^')?..].trim_start_matches('^').trim();
+
+		(!label.is_empty()).then(|| label.to_owned())
+	}
+
+	pub fn embed(diagnostics: &[Self]) -> Result<Embed> {
+		let errors = diagnostics
+			.iter()
+			.filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+			.count();
+		let warnings = diagnostics.len() - errors;
+
+		let title = "Compiler diagnostics";
+		let description = format!(
+			"{} error{}, {} warning{}",
+			errors,
+			if errors == 1 { "" } else { "s" },
+			warnings,
+			if warnings == 1 { "" } else { "s" },
+		);
+
+		let fields = diagnostics
+			.iter()
+			.map(|diagnostic| (diagnostic.title(), diagnostic.details()))
+			.collect();
+
+		let embed_builder = EmbedBuilder::new()
+			.color(STARLIGHT_COLORS[usize::from(errors > 0)].to_decimal())
+			.title(title)
+			.description(&description);
+
+		add_embed_fields(embed_builder, title.len() + description.len(), fields)
+			.build()
+			.into_diagnostic()
+	}
+
+	#[must_use]
+	pub fn details(&self) -> String {
+		let mut details = self.span.as_ref().map_or_else(
+			|| "no location".to_owned(),
+			|span| {
+				span.label.as_ref().map_or_else(
+					|| format!("`{}`", span),
+					|label| format!("`{}` {}", span, label),
+				)
+			},
+		);
+
+		for (level, message) in &self.children {
+			let _ = write!(details, "\n**{}:** {}", level, message);
+		}
+
+		details
+	}
+
+	#[must_use]
+	pub fn title(&self) -> String {
+		self.code.as_ref().map_or_else(
+			|| format!("{}: {}", self.level, self.message),
+			|code| format!("{}[{}]: {}", self.level, code, self.message),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Diagnostic, DiagnosticLevel, DiagnosticSpan};
+
+	const MOVED_VALUE: &str = include_str!("fixtures/moved_value.stderr");
+	const MISMATCHED_TYPES: &str = include_str!("fixtures/mismatched_types.stderr");
+
+	#[test]
+	fn parse_moved_value() {
+		let diagnostics = Diagnostic::parse_all(MOVED_VALUE);
+
+		assert_eq!(diagnostics.len(), 3);
+
+		assert_eq!(
+			diagnostics[0],
+			Diagnostic {
+				level: DiagnosticLevel::Error,
+				code: Some("E0382".to_owned()),
+				message: "borrow of moved value: `x`".to_owned(),
+				span: Some(DiagnosticSpan {
+					file: "src/main.rs".to_owned(),
+					line: 4,
+					column: 20,
+					label: Some("value borrowed here after move".to_owned()),
+				}),
+				children: vec![(
+					DiagnosticLevel::Help,
+					"consider cloning the value if the performance cost is acceptable".to_owned()
+				)],
+			}
+		);
+
+		assert_eq!(diagnostics[1].level, DiagnosticLevel::Warning);
+		assert_eq!(diagnostics[1].code, None);
+		assert_eq!(diagnostics[1].message, "unused variable: `y`");
+		assert_eq!(
+			diagnostics[1].children,
+			[(
+				DiagnosticLevel::Note,
+				"`#[warn(unused_variables)]` (part of `#[warn(unused)]`) on by default".to_owned()
+			)]
+		);
+
+		assert_eq!(diagnostics[2].title(), "warning: unused variable: `unused`");
+		assert!(diagnostics[2].children.is_empty());
+	}
+
+	#[test]
+	fn parse_mismatched_types() {
+		let diagnostics = Diagnostic::parse_all(MISMATCHED_TYPES);
+
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].title(), "error[E0308]: mismatched types");
+		assert_eq!(
+			diagnostics[0].span.as_ref().map(ToString::to_string),
+			Some("src/main.rs:5:22".to_owned())
+		);
+		assert_eq!(
+			diagnostics[0]
+				.span
+				.as_ref()
+				.and_then(|span| span.label.as_deref()),
+			Some("expected `u32`, found `&str`")
+		);
+	}
+
+	#[test]
+	fn parse_no_diagnostics() {
+		assert!(
+			Diagnostic::parse_all("thread 'main' panicked at 'oops', src/main.rs:2:5").is_empty()
+		);
+	}
+}
@@ -0,0 +1,126 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+	pub name: String,
+	pub output: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestReport {
+	pub passed: usize,
+	pub failed: usize,
+	pub ignored: usize,
+	pub failures: Vec<TestFailure>,
+}
+
+impl TestReport {
+	// returns `None` if no tests were run, which usually means the code failed to compile
+	#[must_use]
+	pub fn parse(output: &str) -> Option<Self> {
+		let mut report = Self::default();
+		let mut found = false;
+		let mut lines = output.lines().peekable();
+
+		while let Some(line) = lines.next() {
+			if let Some(summary) = line.strip_prefix("test result: ") {
+				found = true;
+				report.add_summary(summary);
+			} else if let Some(name) = line
+				.strip_prefix("---- ")
+				.and_then(|rest| rest.strip_suffix(" stdout ----"))
+			{
+				let mut output = Vec::new();
+
+				while let Some(line) =
+					lines.next_if(|line| !line.starts_with("---- ") && *line != "failures:")
+				{
+					output.push(line);
+				}
+
+				report.failures.push(TestFailure {
+					name: name.to_owned(),
+					output: output.join("\n").trim().to_owned(),
+				});
+			}
+		}
+
+		found.then(|| report)
+	}
+
+	#[must_use]
+	pub const fn is_success(&self) -> bool {
+		self.failed == 0
+	}
+
+	// `ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s`
+	fn add_summary(&mut self, summary: &str) {
+		let counts = summary
+			.split_once(". ")
+			.map_or(summary, |(_, counts)| counts);
+
+		for count in counts.split("; ") {
+			let (number, kind) = match count.split_once(' ') {
+				Some((number, kind)) => (number.parse::<usize>().unwrap_or_default(), kind),
+				None => continue,
+			};
+
+			match kind {
+				"passed" => self.passed += number,
+				"failed" => self.failed += number,
+				"ignored" => self.ignored += number,
+				_ => {}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{TestFailure, TestReport};
+
+	const OUTPUT: &str = "
+running 3 tests
+test tests::ignored ... ignored
+test tests::passes ... ok
+test tests::fails ... FAILED
+
+failures:
+
+---- tests::fails stdout ----
+thread 'tests::fails' panicked at 'assertion failed: `(left == right)`
+  left: `1`,
+ right: `2`', src/lib.rs:9:9
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::fails
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+	#[test]
+	fn parse_report() {
+		let report = TestReport::parse(OUTPUT).unwrap();
+
+		assert_eq!(report.passed, 1);
+		assert_eq!(report.failed, 1);
+		assert_eq!(report.ignored, 1);
+		assert!(!report.is_success());
+		assert_eq!(
+			report.failures,
+			[TestFailure {
+				name: "tests::fails".to_owned(),
+				output: "thread 'tests::fails' panicked at 'assertion failed: `(left == right)`\n  left: `1`,\n right: `2`', src/lib.rs:9:9\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace".to_owned(),
+			}]
+		);
+	}
+
+	#[test]
+	fn parse_no_tests() {
+		assert_eq!(
+			TestReport::parse("error[E0425]: cannot find value `x`"),
+			None
+		);
+	}
+}
@@ -0,0 +1,131 @@
+use reqwest::Url;
+
+use super::{BuildMode, Edition, RustChannel};
+
+const PLAYGROUND_HOST: &str = "play.rust-lang.org";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaygroundLink {
+	pub gist: String,
+	pub channel: Option<RustChannel>,
+	pub edition: Option<Edition>,
+	pub mode: Option<BuildMode>,
+}
+
+impl PlaygroundLink {
+	// links are only taken from the official playground or the one the bot is configured to use
+	#[must_use]
+	pub fn parse(input: &str, playground_url: &str) -> Option<Self> {
+		let input = input.trim().trim_start_matches('<').trim_end_matches('>');
+
+		if Self::is_gist_id(input) {
+			return Some(Self {
+				gist: input.to_owned(),
+				channel: None,
+				edition: None,
+				mode: None,
+			});
+		}
+
+		let url = Url::parse(input).ok()?;
+
+		if !matches!(url.scheme(), "http" | "https") || !Self::is_playground(&url, playground_url) {
+			return None;
+		}
+
+		let mut link = Self {
+			gist: String::new(),
+			channel: None,
+			edition: None,
+			mode: None,
+		};
+
+		for (key, value) in url.query_pairs() {
+			match key.as_ref() {
+				"gist" => link.gist = value.into_owned(),
+				"version" => link.channel = value.parse().ok(),
+				"edition" => link.edition = value.parse().ok(),
+				"mode" => link.mode = value.parse().ok(),
+				_ => {}
+			}
+		}
+
+		Self::is_gist_id(&link.gist).then(|| link)
+	}
+
+	fn is_playground(url: &Url, playground_url: &str) -> bool {
+		let configured = Url::parse(playground_url).ok();
+
+		url.host_str().map_or(false, |host| {
+			host == PLAYGROUND_HOST || configured.as_ref().and_then(Url::host_str) == Some(host)
+		})
+	}
+
+	fn is_gist_id(input: &str) -> bool {
+		!input.is_empty() && input.chars().all(|c| c.is_ascii_alphanumeric())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PlaygroundLink;
+	use crate::helpers::playground::{BuildMode, Edition, RustChannel};
+
+	const PLAYGROUND_URL: &str = "https://play.example.com";
+
+	#[test]
+	fn parse_url() {
+		let link = PlaygroundLink::parse(
+			"https://play.rust-lang.org/?version=stable&mode=release&edition=2021&gist=0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9",
+			PLAYGROUND_URL,
+		)
+		.unwrap();
+
+		assert_eq!(link.gist, "0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9");
+		assert_eq!(link.channel, Some(RustChannel::Stable));
+		assert_eq!(link.edition, Some(Edition::E2021));
+		assert_eq!(link.mode, Some(BuildMode::Release));
+	}
+
+	#[test]
+	fn parse_gist_id() {
+		let link =
+			PlaygroundLink::parse("0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9", PLAYGROUND_URL).unwrap();
+
+		assert_eq!(link.gist, "0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9");
+		assert_eq!(link.channel, None);
+		assert_eq!(link.edition, None);
+		assert_eq!(link.mode, None);
+	}
+
+	#[test]
+	fn parse_configured_host() {
+		let link = PlaygroundLink::parse(
+			"https://play.example.com/?gist=0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9",
+			PLAYGROUND_URL,
+		)
+		.unwrap();
+
+		assert_eq!(link.gist, "0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9");
+	}
+
+	#[test]
+	fn parse_invalid() {
+		assert_eq!(
+			PlaygroundLink::parse("https://play.rust-lang.org/", PLAYGROUND_URL),
+			None
+		);
+		assert_eq!(
+			PlaygroundLink::parse("https://play.rust-lang.org/?gist=../meta", PLAYGROUND_URL),
+			None
+		);
+		assert_eq!(
+			PlaygroundLink::parse(
+				"https://evil.example/?gist=0b6a5e2cd5e3b4f0b8e6e0e1c5b2a1f9",
+				PLAYGROUND_URL
+			),
+			None
+		);
+		assert_eq!(PlaygroundLink::parse("not a link", PLAYGROUND_URL), None);
+	}
+}
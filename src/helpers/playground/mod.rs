@@ -3,19 +3,62 @@ use std::{
 	str::FromStr,
 };
 
-use crate::prelude::*;
+use twilight_model::application::interaction::application_command::CommandOptionValue;
 
+use crate::{helpers::parsing::CommandParse, prelude::*};
+
+pub mod ansi;
+mod cache;
+mod client;
+mod compare;
+mod diagnostic;
+mod libtest;
+mod link;
+mod output;
 mod request;
 mod response;
+mod session;
 mod util;
 
-pub use self::{request::*, response::*, util::*};
+pub use self::{
+	cache::{PlaygroundCache, PlaygroundCacheKey},
+	client::{PlaygroundClient, PlaygroundEndpoint},
+	compare::PlaygroundComparison,
+	diagnostic::{Diagnostic, DiagnosticLevel, DiagnosticSpan},
+	libtest::{TestFailure, TestReport},
+	link::PlaygroundLink,
+	output::PlaygroundOutput,
+	request::*,
+	response::*,
+	session::{PlaygroundSession, PlaygroundSessions},
+	util::*,
+};
 
 #[derive(Debug, Default, Error, Clone, Copy)]
 #[error("an invalid type was given")]
 pub struct InvalidTypeError;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+macro_rules! impl_command_parse {
+	($($ty:ty),*) => {
+		$(
+			impl CommandParse<$ty> for CommandOptionValue {
+				fn parse_option(self) -> Option<$ty> {
+					CommandParse::<String>::parse_option(self)?.parse().ok()
+				}
+			}
+		)*
+	};
+}
+
+impl_command_parse!(
+	RustChannel,
+	Edition,
+	BuildMode,
+	CompileTarget,
+	AssemblyFlavor
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RustChannel {
 	Stable,
@@ -52,7 +95,7 @@ impl FromStr for RustChannel {
 	}
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Edition {
 	#[serde(rename = "2015")]
 	E2015,
@@ -91,7 +134,7 @@ impl FromStr for Edition {
 	}
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CrateType {
 	#[serde(rename = "bin")]
 	Binary,
@@ -99,7 +142,18 @@ pub enum CrateType {
 	Library,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl CrateType {
+	#[must_use]
+	pub fn detect(code: &str) -> Self {
+		if code.contains("fn main") {
+			Self::Binary
+		} else {
+			Self::Library
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BuildMode {
 	Debug,
@@ -132,3 +186,110 @@ impl FromStr for BuildMode {
 		}
 	}
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum CompileTarget {
+	#[serde(rename = "asm")]
+	Assembly,
+	#[serde(rename = "llvm-ir")]
+	LlvmIr,
+	#[serde(rename = "mir")]
+	Mir,
+	#[serde(rename = "hir")]
+	Hir,
+	#[serde(rename = "wasm")]
+	Wasm,
+}
+
+impl Default for CompileTarget {
+	fn default() -> Self {
+		Self::Assembly
+	}
+}
+
+impl Display for CompileTarget {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		match self {
+			Self::Assembly => f.write_str("asm"),
+			Self::LlvmIr => f.write_str("llvm-ir"),
+			Self::Mir => f.write_str("mir"),
+			Self::Hir => f.write_str("hir"),
+			Self::Wasm => f.write_str("wasm"),
+		}
+	}
+}
+
+impl FromStr for CompileTarget {
+	type Err = InvalidTypeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"asm" => Ok(Self::Assembly),
+			"llvm-ir" => Ok(Self::LlvmIr),
+			"mir" => Ok(Self::Mir),
+			"hir" => Ok(Self::Hir),
+			"wasm" => Ok(Self::Wasm),
+			_ => Err(InvalidTypeError::default()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssemblyFlavor {
+	Att,
+	Intel,
+}
+
+impl Default for AssemblyFlavor {
+	fn default() -> Self {
+		Self::Att
+	}
+}
+
+impl Display for AssemblyFlavor {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		match self {
+			Self::Att => f.write_str("att"),
+			Self::Intel => f.write_str("intel"),
+		}
+	}
+}
+
+impl FromStr for AssemblyFlavor {
+	type Err = InvalidTypeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"att" => Ok(Self::Att),
+			"intel" => Ok(Self::Intel),
+			_ => Err(InvalidTypeError::default()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DemangleAssembly {
+	Demangle,
+	Mangle,
+}
+
+impl Default for DemangleAssembly {
+	fn default() -> Self {
+		Self::Demangle
+	}
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessAssembly {
+	Filter,
+	Raw,
+}
+
+impl Default for ProcessAssembly {
+	fn default() -> Self {
+		Self::Filter
+	}
+}
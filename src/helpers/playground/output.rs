@@ -0,0 +1,170 @@
+use super::{ansi, truncate, Diagnostic, PlaygroundClient, PlaygroundRequest, PlaygroundResponse};
+use crate::{
+	helpers::{error_codes, parsing::CodeBlock},
+	prelude::*,
+	slashies::{components::ExplainMenu, SlashData},
+};
+
+// leaves enough room in the message for the code block fences and the share link
+const PREVIEW_LENGTH: usize = 1500;
+
+const CACHED_NOTE: &str = "\n*cached result, use `fresh` or a re-run button to skip the cache*";
+
+#[derive(Debug, Clone, Default)]
+#[must_use = "PlaygroundOutput has no side effects"]
+pub struct PlaygroundOutput {
+	pub content: String,
+	pub attachments: Vec<(String, Vec<u8>)>,
+	pub error_codes: Vec<&'static str>,
+	pub diagnostics: Vec<Diagnostic>,
+	pub program_stderr: String,
+	pub stdout: String,
+	pub cached: bool,
+}
+
+impl PlaygroundOutput {
+	pub async fn new(
+		client: &PlaygroundClient,
+		response: &PlaygroundResponse,
+		request: &PlaygroundRequest<'_>,
+		ansi: bool,
+	) -> Self {
+		let mut content = response.render(ansi);
+
+		if response.cached {
+			content.push_str(CACHED_NOTE);
+		}
+
+		let error_codes = error_codes::find(response.compiler_stderr());
+		let diagnostics = Diagnostic::parse_all(response.compiler_stderr());
+		let program_stderr = response.program_stderr.trim().to_owned();
+		let stdout = response.stdout.trim().to_owned();
+
+		if content.len() <= SlashData::MAX_CONTENT_LENGTH {
+			return Self {
+				content,
+				attachments: Vec::new(),
+				error_codes,
+				diagnostics,
+				program_stderr,
+				stdout,
+				cached: response.cached,
+			};
+		}
+
+		let outputs = [
+			("stderr", response.stderr.trim(), ansi),
+			("stdout", response.stdout.trim(), false),
+		]
+		.into_iter()
+		.filter(|(_, output, _)| !output.is_empty())
+		.collect::<Vec<_>>();
+
+		let preview_length = PREVIEW_LENGTH / outputs.len().max(1);
+
+		let mut content = String::new();
+		let mut attachments = Vec::with_capacity(outputs.len());
+
+		for (name, output, ansi) in outputs {
+			content += &ansi::preview(output, preview_length, ansi).to_string();
+			content.push('\n');
+
+			attachments.push((format!("{}.txt", name), output.as_bytes().to_vec()));
+		}
+
+		content.push_str("the output was too long, so the full output has been attached");
+
+		match client.share(request).await {
+			Ok(url) => {
+				let _ = write!(content, "\n[view on the playground](<{}>)", url);
+			}
+			Err(e) => event!(
+				Level::WARN,
+				error = &*e.root_cause(),
+				"failed to create share link"
+			),
+		}
+
+		if response.cached {
+			content.push_str(CACHED_NOTE);
+		}
+
+		Self {
+			content,
+			attachments,
+			error_codes,
+			diagnostics,
+			program_stderr,
+			stdout,
+			cached: response.cached,
+		}
+	}
+
+	pub fn stderr(stderr: &str) -> Self {
+		let stderr = stderr.trim();
+		let content = CodeBlock::new(stderr, None).to_string();
+		let error_codes = error_codes::find(stderr);
+
+		if content.len() <= SlashData::MAX_CONTENT_LENGTH {
+			return Self {
+				content,
+				error_codes,
+				..Self::default()
+			};
+		}
+
+		let preview = truncate(stderr, PREVIEW_LENGTH);
+
+		Self {
+			content: format!(
+				"{}\nthe output was too long, so the full output has been attached",
+				CodeBlock::new(&format!("{}\n...", preview), None)
+			),
+			attachments: vec![("stderr.txt".to_owned(), stderr.as_bytes().to_vec())],
+			error_codes,
+			..Self::default()
+		}
+	}
+
+	#[must_use]
+	pub fn program_output(&self) -> String {
+		let outputs = [self.program_stderr.as_str(), self.stdout.as_str()]
+			.into_iter()
+			.filter(|output| !output.is_empty())
+			.collect::<Vec<_>>();
+
+		let preview_length = PREVIEW_LENGTH / outputs.len().max(1);
+
+		let mut content = outputs
+			.into_iter()
+			.map(|output| {
+				let preview = truncate(output, preview_length);
+
+				if preview.len() < output.len() {
+					CodeBlock::new(&format!("{}\n...", preview), None).to_string()
+				} else {
+					CodeBlock::new(preview, None).to_string()
+				}
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+
+		if self.cached {
+			content.push_str(CACHED_NOTE);
+		}
+
+		content
+	}
+
+	pub fn apply(self, data: &mut SlashData) {
+		data.message(self.content);
+
+		for (filename, content) in self.attachments {
+			data.attachment(filename, content);
+		}
+
+		if let Some(component) = ExplainMenu::component(&self.error_codes) {
+			data.components(vec![component]);
+		}
+	}
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::{BuildMode, CrateType, Edition, RustChannel};
+use super::{
+	AssemblyFlavor, BuildMode, CompileTarget, CrateType, DemangleAssembly, Edition,
+	ProcessAssembly, RustChannel,
+};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PlaygroundRequest<'a> {
@@ -29,15 +32,42 @@ impl<'a> PlaygroundRequest<'a> {
 			edition,
 			mode,
 			tests,
-			crate_type: if code.contains("fn main") {
-				CrateType::Binary
-			} else {
-				CrateType::Library
-			},
+			crate_type: CrateType::detect(code),
 		}
 	}
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaygroundCompileRequest<'a> {
+	pub target: CompileTarget,
+	pub assembly_flavor: AssemblyFlavor,
+	pub demangle_assembly: DemangleAssembly,
+	pub process_assembly: ProcessAssembly,
+	#[serde(flatten)]
+	pub request: PlaygroundRequest<'a>,
+}
+
+impl<'a> PlaygroundCompileRequest<'a> {
+	#[must_use]
+	pub fn new(request: PlaygroundRequest<'a>, target: CompileTarget) -> Self {
+		Self {
+			target,
+			assembly_flavor: AssemblyFlavor::default(),
+			demangle_assembly: DemangleAssembly::default(),
+			process_assembly: ProcessAssembly::default(),
+			request,
+		}
+	}
+
+	#[must_use]
+	pub const fn assembly_flavor(mut self, assembly_flavor: AssemblyFlavor) -> Self {
+		self.assembly_flavor = assembly_flavor;
+
+		self
+	}
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MiriRequest<'a> {
 	pub edition: Edition,
@@ -47,3 +77,22 @@ pub struct MiriRequest<'a> {
 pub type MacroExpansionRequest<'a> = MiriRequest<'a>;
 
 pub type FormatRequest<'a> = MiriRequest<'a>;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ClippyRequest<'a> {
+	pub edition: Edition,
+	#[serde(rename = "crateType")]
+	pub crate_type: CrateType,
+	pub code: &'a str,
+}
+
+impl<'a> ClippyRequest<'a> {
+	#[must_use]
+	pub fn new(code: &'a str, edition: Edition) -> Self {
+		Self {
+			edition,
+			crate_type: CrateType::detect(code),
+			code,
+		}
+	}
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Deserializer, Serialize};
 
-use super::extract_relevant_lines;
+use super::{ansi, extract_relevant_lines};
+use crate::{helpers::parsing::CodeBlock, prelude::*};
+
+// miri runs the binary through `cargo-miri runner`, which lives in the playground's rustup directory
+const RUNNING_TOKENS: [&str; 2] = ["Running `target", "Running `/playground"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormatResponse {
@@ -10,11 +14,78 @@ pub struct FormatResponse {
 	pub stderr: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompileResponse {
+	pub success: bool,
+	pub code: String,
+	pub stdout: String,
+	pub stderr: String,
+}
+
+impl CompileResponse {
+	pub fn format(&mut self) {
+		self.stderr = extract_relevant_lines(
+			&self.stderr,
+			&["Compiling playground"],
+			&[
+				"warning emitted",
+				"warnings emitted",
+				"warning: `playground` (",
+				"error: could not compile",
+				"error: aborting",
+				"Finished ",
+			],
+		)
+		.to_owned();
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "PlaygroundResponse")]
+pub struct ClippyResponse {
+	pub success: bool,
+	pub stdout: String,
+	pub stderr: String,
+}
+
+impl ClippyResponse {
+	pub fn format(&mut self) {
+		self.stderr = extract_relevant_lines(
+			&self.stderr,
+			&["Checking playground"],
+			&[
+				"warning emitted",
+				"warnings emitted",
+				"warning: `playground` (",
+				"error: could not compile",
+				"error: aborting",
+				"Finished ",
+			],
+		)
+		.to_owned();
+	}
+}
+
+impl From<PlaygroundResponse> for ClippyResponse {
+	fn from(response: PlaygroundResponse) -> Self {
+		Self {
+			success: response.success,
+			stdout: response.stdout,
+			stderr: response.stderr,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PlaygroundResponse {
 	pub success: bool,
 	pub stdout: String,
 	pub stderr: String,
+	// the part of `stderr` that the program printed itself, set by `format`
+	#[serde(skip)]
+	pub program_stderr: String,
+	#[serde(skip)]
+	pub cached: bool,
 }
 
 impl PlaygroundResponse {
@@ -32,8 +103,13 @@ impl PlaygroundResponse {
 			],
 		);
 
-		let output = if self.stderr.contains("Running `target") {
-			let program_stderr = extract_relevant_lines(&self.stderr, &["Running `target"], &[]);
+		let output = if RUNNING_TOKENS
+			.iter()
+			.any(|token| self.stderr.contains(token))
+		{
+			let program_stderr = extract_relevant_lines(&self.stderr, &RUNNING_TOKENS, &[]);
+
+			self.program_stderr = program_stderr.to_owned();
 
 			if show_compiler_warnings {
 				match (compiler_output, program_stderr) {
@@ -51,6 +127,43 @@ impl PlaygroundResponse {
 
 		self.stderr = output;
 	}
+
+	#[must_use]
+	pub fn compiler_stderr(&self) -> &str {
+		self.stderr
+			.strip_suffix(self.program_stderr.as_str())
+			.unwrap_or(&self.stderr)
+	}
+}
+
+impl PlaygroundResponse {
+	#[must_use]
+	pub fn render(&self, ansi: bool) -> String {
+		let stderr = self.stderr.trim();
+		let stdout = self.stdout.trim();
+
+		if stderr.is_empty() && stdout.is_empty() {
+			return "no output".to_owned();
+		}
+
+		let mut blocks = Vec::with_capacity(2);
+
+		if !stderr.is_empty() {
+			blocks.push(ansi::code_block(stderr, ansi).to_string());
+		}
+
+		if !stdout.is_empty() {
+			blocks.push(CodeBlock::new(stdout, None).to_string());
+		}
+
+		blocks.join("\n")
+	}
+}
+
+impl Display for PlaygroundResponse {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str(&self.render(false))
+	}
 }
 
 impl<'de> Deserialize<'de> for PlaygroundResponse {
@@ -80,12 +193,56 @@ impl<'de> Deserialize<'de> for PlaygroundResponse {
 				success,
 				stdout,
 				stderr,
+				program_stderr: String::new(),
+				cached: false,
 			},
 			RawPlayResponse::Err { error } => Self {
 				success: false,
 				stdout: String::new(),
 				stderr: error,
+				program_stderr: String::new(),
+				cached: false,
 			},
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::PlaygroundResponse;
+	use crate::helpers::playground::{Diagnostic, DiagnosticLevel};
+
+	const STDERR: &str = "   Compiling playground v0.0.1 (/playground)\nwarning: unused variable: `x`\n --> src/main.rs:2:9\n  |\n2 |     let x = 1;\n  |         ^ help: if this is intentional, prefix it with an underscore: `_x`\n  |\n  = note: `#[warn(unused_variables)]` on by default\n\nwarning: `playground` (bin \"playground\") generated 1 warning\n    Finished dev [unoptimized + debuginfo] target(s) in 0.50s\n     Running `target/debug/playground`\nerror: the program's own message\n";
+
+	fn raw_response() -> PlaygroundResponse {
+		PlaygroundResponse {
+			success: true,
+			stdout: String::new(),
+			stderr: STDERR.to_owned(),
+			program_stderr: String::new(),
+			cached: false,
+		}
+	}
+
+	#[test]
+	fn compiler_stderr_excludes_program_stderr() {
+		let mut response = raw_response();
+		response.format(true);
+
+		assert_eq!(
+			response.program_stderr,
+			"error: the program's own message\n"
+		);
+		assert!(response.stderr.ends_with(&response.program_stderr));
+
+		let diagnostics = Diagnostic::parse_all(response.compiler_stderr());
+
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+
+		let mut response = raw_response();
+		response.format(false);
+
+		assert!(Diagnostic::parse_all(response.compiler_stderr()).is_empty());
+	}
+}
@@ -0,0 +1,93 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use twilight_model::id::{
+	marker::{MessageMarker, UserMarker},
+	Id,
+};
+
+use super::{
+	BuildMode, Edition, PlaygroundClient, PlaygroundOutput, PlaygroundRequest, RustChannel,
+};
+use crate::prelude::*;
+
+const MAX_SESSIONS: usize = 512;
+
+// `code` is stored after `ResultHandling` has been applied, so it can be re-run and shared as-is
+#[derive(Debug, Clone)]
+pub struct PlaygroundSession {
+	pub code: String,
+	pub channel: RustChannel,
+	pub edition: Edition,
+	pub mode: BuildMode,
+	pub warnings: bool,
+	pub author: Id<UserMarker>,
+	pub ephemeral: bool,
+	pub ansi: bool,
+	pub fresh: bool,
+}
+
+impl PlaygroundSession {
+	#[must_use]
+	pub fn request(&self) -> PlaygroundRequest<'_> {
+		PlaygroundRequest::new(&self.code, self.channel, self.edition, self.mode, false)
+	}
+
+	pub async fn run(&self, client: &PlaygroundClient) -> Result<PlaygroundOutput> {
+		let request = self.request();
+
+		let mut response = if self.fresh {
+			client.execute_uncached(&request).await?
+		} else {
+			client.execute(&request).await?
+		};
+
+		response.format(self.warnings);
+
+		Ok(PlaygroundOutput::new(client, &response, &request, self.ansi).await)
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct PlaygroundSessions {
+	sessions: Mutex<VecDeque<(Id<MessageMarker>, PlaygroundSession, PlaygroundOutput)>>,
+}
+
+impl PlaygroundSessions {
+	pub fn insert(
+		&self,
+		message_id: Id<MessageMarker>,
+		session: PlaygroundSession,
+		output: PlaygroundOutput,
+	) {
+		let mut sessions = self.sessions.lock().unwrap();
+
+		sessions.retain(|(id, ..)| *id != message_id);
+
+		if sessions.len() >= MAX_SESSIONS {
+			sessions.pop_front();
+		}
+
+		sessions.push_back((message_id, session, output));
+	}
+
+	#[must_use]
+	pub fn get(
+		&self,
+		message_id: Id<MessageMarker>,
+	) -> Option<(PlaygroundSession, PlaygroundOutput)> {
+		self.sessions
+			.lock()
+			.unwrap()
+			.iter()
+			.find(|(id, ..)| *id == message_id)
+			.map(|(_, session, output)| (session.clone(), output.clone()))
+	}
+
+	pub fn remove(&self, message_id: Id<MessageMarker>) -> Option<PlaygroundSession> {
+		let mut sessions = self.sessions.lock().unwrap();
+
+		let position = sessions.iter().position(|(id, ..)| *id == message_id)?;
+
+		sessions.remove(position).map(|(_, session, _)| session)
+	}
+}
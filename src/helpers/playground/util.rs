@@ -1,6 +1,16 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::borrow::Cow;
 
-use crate::{prelude::*, state::Context};
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+
+use super::CompileTarget;
+
+// discord allows at most 25 fields per embed, with names of up to 256 characters, values of up to
+// 1024, and 6000 characters across the whole embed
+const EMBED_MAX_FIELDS: usize = 25;
+pub const EMBED_FIELD_NAME_LENGTH: usize = 256;
+pub const EMBED_FIELD_VALUE_LENGTH: usize = 1024;
+const EMBED_LENGTH: usize = 6000;
+const EMBED_FOOTER_LENGTH: usize = 32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ResultHandling {
@@ -100,22 +110,150 @@ pub(super) fn extract_relevant_lines<'a>(
 	stderr
 }
 
-pub async fn get_gist(context: Context, code: &str) -> Result<String> {
-	let cdn = context.cdn();
+const HIR_PRELUDE: [&str; 4] = [
+	"#[prelude_import]",
+	"use std::prelude::",
+	"#[macro_use]",
+	"extern crate std;",
+];
+
+fn is_ident_char(c: char) -> bool {
+	c.is_alphanumeric() || c == '_'
+}
+
+fn function_names(code: &str) -> Vec<&str> {
+	code.match_indices("fn ")
+		.filter(|(index, _)| !code[..*index].ends_with(is_ident_char))
+		.filter_map(|(index, _)| {
+			let rest = code[(index + 3)..].trim_start();
+			let end = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+
+			if end == 0 {
+				None
+			} else {
+				Some(&rest[..end])
+			}
+		})
+		.collect()
+}
+
+fn contains_item(haystack: &str, needle: &str) -> bool {
+	haystack
+		.match_indices(needle)
+		.any(|(index, _)| !haystack[(index + needle.len())..].starts_with(is_ident_char))
+}
+
+#[must_use]
+pub fn filter_functions(output: &str, code: &str, target: CompileTarget) -> String {
+	if matches!(target, CompileTarget::Hir) {
+		return output
+			.lines()
+			.filter(|line| {
+				!HIR_PRELUDE
+					.iter()
+					.any(|prelude| line.trim_start().starts_with(prelude))
+			})
+			.collect::<Vec<_>>()
+			.join("\n")
+			.trim()
+			.to_owned();
+	}
+
+	let names = function_names(code);
+
+	let blocks = output
+		.split("\n\n")
+		.filter(|block| {
+			let header = block
+				.lines()
+				.find(|line| !line.starts_with("//"))
+				.unwrap_or_default();
+
+			names.iter().any(|name| {
+				(header.contains("playground") && contains_item(header, &format!("::{}", name)))
+					|| (header.starts_with("fn ") && contains_item(header, &format!("fn {}", name)))
+			})
+		})
+		.collect::<Vec<_>>();
+
+	if blocks.is_empty() {
+		output.trim().to_owned()
+	} else {
+		blocks.join("\n\n")
+	}
+}
+
+#[must_use]
+pub fn truncate(text: &str, max: usize) -> &str {
+	if text.len() <= max {
+		return text;
+	}
+
+	let mut end = max;
+
+	while !text.is_char_boundary(end) {
+		end -= 1;
+	}
+
+	&text[..end]
+}
+
+// `used` is the length of everything already in the embed
+pub fn add_embed_fields(
+	mut embed_builder: EmbedBuilder,
+	used: usize,
+	fields: Vec<(String, String)>,
+) -> EmbedBuilder {
+	let total = fields.len();
+	let mut remaining = EMBED_LENGTH.saturating_sub(used + EMBED_FOOTER_LENGTH);
+	let mut added = 0;
+
+	for (name, value) in fields.into_iter().take(EMBED_MAX_FIELDS) {
+		let name = truncate(&name, EMBED_FIELD_NAME_LENGTH);
+		let value = truncate(&value, EMBED_FIELD_VALUE_LENGTH);
+
+		if name.len() + value.len() > remaining {
+			break;
+		}
+
+		remaining -= name.len() + value.len();
+		added += 1;
+		embed_builder = embed_builder.field(EmbedFieldBuilder::new(name, value));
+	}
+
+	if added < total {
+		embed_builder = embed_builder.footer(EmbedFooterBuilder::new(format!(
+			"…and {} more",
+			total - added
+		)));
+	}
 
-	let mut payload = HashMap::new();
-	payload.insert("code", code);
+	embed_builder
+}
 
-	let resp = cdn
-		.post("https://play.rust-lang.org/meta/gist")
-		.json(&payload)
-		.send()
-		.await
-		.into_diagnostic()?;
+#[cfg(test)]
+mod tests {
+	use super::{filter_functions, CompileTarget};
 
-	let mut resp: HashMap<String, String> = resp.json().await.into_diagnostic()?;
+	#[test]
+	fn filter_assembly() {
+		let code = "pub fn add(a: i32, b: i32) -> i32 { a + b }\nfn main() {}";
+		let output = "core::fmt::Arguments::new_v1:\n\tpushq\t%rax\n\tretq\n\nplayground::add:\n\tleal\t(%rdi,%rsi), %eax\n\tretq\n\nplayground::add_one:\n\tretq\n\nplayground::main:\n\tretq";
 
-	event!(Level::INFO, "gist response: {:?}", resp);
+		assert_eq!(
+			filter_functions(output, code, CompileTarget::Assembly),
+			"playground::add:\n\tleal\t(%rdi,%rsi), %eax\n\tretq\n\nplayground::main:\n\tretq"
+		);
+	}
 
-	resp.remove("id").ok_or_else(|| error!("no gist found"))
+	#[test]
+	fn filter_mir() {
+		let code = "fn main() {\n    let _ = 1;\n}";
+		let output = "// WARNING: This output format is intended for human consumers only\n// and is subject to change without notice. Knock yourself out.\nfn main() -> () {\n    let mut _0: ();\n}\n\nfn std::rt::lang_start() -> isize {\n}";
+
+		assert_eq!(
+			filter_functions(output, code, CompileTarget::Mir),
+			"// WARNING: This output format is intended for human consumers only\n// and is subject to change without notice. Knock yourself out.\nfn main() -> () {\n    let mut _0: ();\n}"
+		);
+	}
 }
@@ -0,0 +1,263 @@
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	str::FromStr,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+use twilight_model::id::{
+	marker::{GuildMarker, UserMarker},
+	Id,
+};
+
+use crate::prelude::*;
+
+// buckets that have fully refilled are dropped once a map grows past this
+const MAX_BUCKETS: usize = 4096;
+
+#[derive(Debug, Error, Clone, Copy)]
+#[error("rate limits must be written as `requests/seconds`, with both being at least 1")]
+pub struct RateLimitParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+	requests: u32,
+	seconds: u64,
+}
+
+impl RateLimit {
+	#[must_use]
+	pub const fn new(requests: u32, seconds: u64) -> Option<Self> {
+		if requests == 0 || seconds == 0 {
+			None
+		} else {
+			Some(Self { requests, seconds })
+		}
+	}
+
+	#[must_use]
+	pub const fn requests(self) -> u32 {
+		self.requests
+	}
+
+	#[must_use]
+	pub const fn seconds(self) -> u64 {
+		self.seconds
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	fn tokens_per_second(self) -> f64 {
+		f64::from(self.requests) / self.seconds as f64
+	}
+}
+
+impl Display for RateLimit {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}/{}", self.requests, self.seconds)
+	}
+}
+
+impl FromStr for RateLimit {
+	type Err = RateLimitParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (requests, seconds) = s.trim().split_once('/').ok_or(RateLimitParseError)?;
+
+		Self::new(
+			requests.trim().parse().map_err(|_| RateLimitParseError)?,
+			seconds.trim().parse().map_err(|_| RateLimitParseError)?,
+		)
+		.ok_or(RateLimitParseError)
+	}
+}
+
+#[must_use]
+pub fn retry_message(retry_after: Duration) -> String {
+	format!(
+		"you're sending too many requests, try again in {}s",
+		retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)
+	)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+	pub user: RateLimit,
+	pub guild: RateLimit,
+}
+
+impl Default for RateLimits {
+	fn default() -> Self {
+		Self {
+			user: RateLimit {
+				requests: 5,
+				seconds: 30,
+			},
+			guild: RateLimit {
+				requests: 30,
+				seconds: 60,
+			},
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+	tokens: f64,
+	updated: Instant,
+}
+
+impl TokenBucket {
+	fn new(limit: RateLimit, now: Instant) -> Self {
+		Self {
+			tokens: f64::from(limit.requests),
+			updated: now,
+		}
+	}
+
+	fn refill(&mut self, limit: RateLimit, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+
+		self.tokens = elapsed
+			.mul_add(limit.tokens_per_second(), self.tokens)
+			.min(f64::from(limit.requests));
+		self.updated = now;
+	}
+
+	fn retry_after(&self, limit: RateLimit) -> Option<Duration> {
+		(self.tokens < 1.0)
+			.then(|| Duration::from_secs_f64((1.0 - self.tokens) / limit.tokens_per_second()))
+	}
+
+	fn is_full(&self, limit: RateLimit, now: Instant) -> bool {
+		now.saturating_duration_since(self.updated) >= Duration::from_secs(limit.seconds)
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+	users: Mutex<HashMap<Id<UserMarker>, TokenBucket>>,
+	guilds: Mutex<HashMap<Id<GuildMarker>, TokenBucket>>,
+}
+
+impl RateLimiter {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// a token is only taken when both buckets have one, so a request denied by the guild doesn't count against the user
+	#[allow(clippy::significant_drop_tightening)]
+	pub fn check(
+		&self,
+		user_id: Id<UserMarker>,
+		guild_id: Option<Id<GuildMarker>>,
+		limits: RateLimits,
+		now: Instant,
+	) -> Result<(), Duration> {
+		let mut users = self.users.lock().unwrap();
+		let mut guilds = self.guilds.lock().unwrap();
+
+		prune(&mut users, limits.user, now);
+		prune(&mut guilds, limits.guild, now);
+
+		let user = users
+			.entry(user_id)
+			.or_insert_with(|| TokenBucket::new(limits.user, now));
+		user.refill(limits.user, now);
+
+		let mut guild = guild_id.map(|guild_id| {
+			let guild = guilds
+				.entry(guild_id)
+				.or_insert_with(|| TokenBucket::new(limits.guild, now));
+			guild.refill(limits.guild, now);
+
+			guild
+		});
+
+		let retry_after = [
+			user.retry_after(limits.user),
+			guild
+				.as_ref()
+				.and_then(|guild| guild.retry_after(limits.guild)),
+		]
+		.into_iter()
+		.flatten()
+		.max();
+
+		if let Some(retry_after) = retry_after {
+			return Err(retry_after);
+		}
+
+		user.tokens -= 1.0;
+
+		if let Some(guild) = &mut guild {
+			guild.tokens -= 1.0;
+		}
+
+		Ok(())
+	}
+}
+
+fn prune<K: Eq + Hash>(buckets: &mut HashMap<K, TokenBucket>, limit: RateLimit, now: Instant) {
+	if buckets.len() >= MAX_BUCKETS {
+		buckets.retain(|_, bucket| !bucket.is_full(limit, now));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use twilight_model::id::Id;
+
+	use super::{RateLimit, RateLimiter, RateLimits};
+
+	fn limits(user: &str, guild: &str) -> RateLimits {
+		RateLimits {
+			user: user.parse().unwrap(),
+			guild: guild.parse().unwrap(),
+		}
+	}
+
+	#[test]
+	fn parse_rate_limit() {
+		assert_eq!("5/30".parse::<RateLimit>().ok(), RateLimit::new(5, 30));
+		assert!("0/30".parse::<RateLimit>().is_err());
+		assert!("5".parse::<RateLimit>().is_err());
+	}
+
+	#[test]
+	fn user_bucket_refills() {
+		let limiter = RateLimiter::new();
+		let limits = limits("2/10", "100/10");
+		let user = Id::new(1);
+		let now = Instant::now();
+
+		assert!(limiter.check(user, None, limits, now).is_ok());
+		assert!(limiter.check(user, None, limits, now).is_ok());
+		assert_eq!(
+			limiter.check(user, None, limits, now),
+			Err(Duration::from_secs(5))
+		);
+
+		assert!(limiter
+			.check(user, None, limits, now + Duration::from_secs(5))
+			.is_ok());
+		assert!(limiter.check(Id::new(2), None, limits, now).is_ok());
+	}
+
+	#[test]
+	fn guild_bucket_is_shared() {
+		let limiter = RateLimiter::new();
+		let limits = limits("5/10", "2/10");
+		let guild = Some(Id::new(1));
+		let now = Instant::now();
+
+		assert!(limiter.check(Id::new(1), guild, limits, now).is_ok());
+		assert!(limiter.check(Id::new(2), guild, limits, now).is_ok());
+		assert!(limiter.check(Id::new(3), guild, limits, now).is_err());
+
+		assert!(limiter.check(Id::new(3), None, limits, now).is_ok());
+	}
+}
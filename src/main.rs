@@ -1,19 +1,17 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use clap::Parser;
 use dotenv::dotenv;
 use starlight::{
 	prelude::*,
-	state::{Config, ContextBuilder, State},
+	state::{Config, ContextBuilder, LogFormat},
 };
 use tokio::runtime::Builder;
 #[cfg(unix)]
 use tokio::signal::unix::{signal, SignalKind};
 #[cfg(windows)]
 use tokio::signal::windows::{ctrl_break, ctrl_c};
-use tracing_subscriber::{fmt, prelude::*, EnvFilter};
-use twilight_cache_inmemory::{InMemoryCacheBuilder, ResourceType};
-use twilight_gateway::Intents;
+use tracing_subscriber::{fmt, prelude::*, reload, EnvFilter};
+use twilight_cache_inmemory::InMemoryCacheBuilder;
 
 static THREAD_ID: AtomicUsize = AtomicUsize::new(1);
 
@@ -35,33 +33,31 @@ fn main() -> Result<()> {
 }
 
 async fn run() -> Result<()> {
-	let mut log_filter_layer = EnvFilter::try_from_default_env()
-		.or_else(|_| EnvFilter::try_new("info"))
-		.into_diagnostic()?;
-	let log_fmt_layer = fmt::layer()
-		.pretty()
-		.with_thread_ids(true)
-		.with_thread_names(true);
+	let config = Config::load()?;
 
-	log_filter_layer = if cfg!(debug_assertions) {
-		log_filter_layer.add_directive("starlight=debug".parse().into_diagnostic()?)
-	} else {
-		log_filter_layer.add_directive("starlight=info".parse().into_diagnostic()?)
-	};
+	#[cfg_attr(not(unix), allow(unused_variables))]
+	let (log_filter_layer, log_filter_handle) = reload::Layer::new(log_filter(&config)?);
 
 	tracing_subscriber::registry()
 		.with(log_filter_layer)
-		.with(log_fmt_layer)
+		.with((config.log_format == LogFormat::Pretty).then(|| {
+			fmt::layer()
+				.pretty()
+				.with_thread_ids(true)
+				.with_thread_names(true)
+		}))
+		.with((config.log_format == LogFormat::Compact).then(|| fmt::layer().compact()))
+		.with((config.log_format == LogFormat::Full).then(fmt::layer))
 		.try_init()
 		.into_diagnostic()?;
 
-	let config = Config::parse();
+	let database_path = config.database_path.clone();
+	let cache = InMemoryCacheBuilder::new().resource_types(config.cache_resource_types);
 	let (client, events) = ContextBuilder::new()
 		.config(config)
-		.intents(Intents::from_bits(3).unwrap_or_else(Intents::all))
-		.shard_builder(|b| b)?
-		.cache(InMemoryCacheBuilder::new().resource_types(ResourceType::all()))
-		.database_path("./target/db")
+		.cluster_builder(|b| b)?
+		.cache(cache)
+		.database_path(database_path)
 		.build()
 		.await?;
 
@@ -82,21 +78,72 @@ async fn run() -> Result<()> {
 	{
 		let mut sigint = signal(SignalKind::interrupt()).into_diagnostic()?;
 		let mut sigterm = signal(SignalKind::terminate()).into_diagnostic()?;
-
-		tokio::select! {
-			_ = sigint.recv() => event!(Level::INFO, "received SIGINT"),
-			_ = sigterm.recv() => event!(Level::INFO, "received SIGTERM"),
-			_ = client.process(events) => (),
-		};
+		let mut sighup = signal(SignalKind::hangup()).into_diagnostic()?;
+
+		let process = client.process(events);
+		tokio::pin!(process);
+
+		loop {
+			tokio::select! {
+				_ = sigint.recv() => {
+					event!(Level::INFO, "received SIGINT");
+					break;
+				}
+				_ = sigterm.recv() => {
+					event!(Level::INFO, "received SIGTERM");
+					break;
+				}
+				_ = sighup.recv() => {
+					event!(Level::INFO, "received SIGHUP, reloading config");
+					reload_config(&client, &log_filter_handle);
+				}
+				_ = &mut process => break,
+			};
+		}
 	}
 
 	event!(Level::INFO, "shutting down");
 
-	client.shutdown();
+	client.shutdown().await;
+
+	Ok(())
+}
+
+#[cfg(unix)]
+fn reload_config(
+	client: &Context,
+	log_filter_handle: &reload::Handle<EnvFilter, tracing_subscriber::Registry>,
+) {
+	let result = client.config().reload().and_then(|config| {
+		let filter = log_filter(&config)?;
+		let reload = client.prepare_reload(config)?;
+
+		log_filter_handle.reload(filter).into_diagnostic()?;
+		reload.apply();
+
+		Ok(())
+	});
+
+	match result {
+		Ok(()) => event!(Level::INFO, "reloaded config"),
+		Err(e) => event!(
+			Level::ERROR,
+			"invalid config, keeping the current one: {:?}",
+			e
+		),
+	}
+}
 
-	let client_ptr = unsafe { Box::from_raw(client.0 as *const State as *mut State) };
+fn log_filter(config: &Config) -> Result<EnvFilter> {
+	if let Some(filter) = &config.log_filter {
+		return EnvFilter::try_new(filter).into_diagnostic();
+	}
 
-	drop(client_ptr);
+	let directive = if cfg!(debug_assertions) {
+		"starlight=debug"
+	} else {
+		"starlight=info"
+	};
 
-	Ok(())
+	Ok(EnvFilter::new("info").add_directive(directive.parse().into_diagnostic()?))
 }
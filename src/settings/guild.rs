@@ -7,11 +7,19 @@ use twilight_model::id::{
 	Id,
 };
 
+use crate::helpers::rate_limit::{RateLimit, RateLimits};
+
 #[allow(clippy::unsafe_derive_deserialize)]
 #[derive(Debug, Clone, IndexEntry, Serialize, Deserialize)]
 pub struct GuildSettings {
 	id: Id<GuildMarker>,
 	tags: Vec<GuildTag>,
+	#[serde(default)]
+	ansi_output: bool,
+	#[serde(default)]
+	user_rate_limit: Option<RateLimit>,
+	#[serde(default)]
+	guild_rate_limit: Option<RateLimit>,
 }
 
 impl GuildSettings {
@@ -20,6 +28,9 @@ impl GuildSettings {
 		Self {
 			id,
 			tags: Vec::new(),
+			ansi_output: false,
+			user_rate_limit: None,
+			guild_rate_limit: None,
 		}
 	}
 
@@ -46,6 +57,31 @@ impl GuildSettings {
 	pub fn tags_mut(&mut self) -> &mut [GuildTag] {
 		&mut self.tags
 	}
+
+	#[must_use]
+	pub const fn ansi_output(&self) -> bool {
+		self.ansi_output
+	}
+
+	pub fn set_ansi_output(&mut self, ansi_output: bool) {
+		self.ansi_output = ansi_output;
+	}
+
+	#[must_use]
+	pub fn rate_limits(&self, defaults: RateLimits) -> RateLimits {
+		RateLimits {
+			user: self.user_rate_limit.unwrap_or(defaults.user),
+			guild: self.guild_rate_limit.unwrap_or(defaults.guild),
+		}
+	}
+
+	pub fn set_user_rate_limit(&mut self, rate_limit: Option<RateLimit>) {
+		self.user_rate_limit = rate_limit;
+	}
+
+	pub fn set_guild_rate_limit(&mut self, rate_limit: Option<RateLimit>) {
+		self.guild_rate_limit = rate_limit;
+	}
 }
 
 impl Default for GuildSettings {
@@ -55,6 +91,9 @@ impl Default for GuildSettings {
 		Self {
 			id: unsafe { Id::new_unchecked(1) },
 			tags: default_tags,
+			ansi_output: false,
+			user_rate_limit: None,
+			guild_rate_limit: None,
 		}
 	}
 }
@@ -14,7 +14,7 @@ pub enum Tables {
 
 impl Tables {
 	#[instrument(skip(context))]
-	pub async fn init(context: Context) -> Result<(), ActionError> {
+	pub async fn init(context: &Context) -> Result<(), ActionError> {
 		Self::init_guilds(context).await?;
 		Ok(())
 	}
@@ -50,7 +50,7 @@ impl Tables {
 		action.run_update_entry(chart).await.into_diagnostic()
 	}
 
-	async fn init_guilds(context: Context) -> Result<(), ActionError> {
+	async fn init_guilds(context: &Context) -> Result<(), ActionError> {
 		let default = GuildSettings::default();
 		event!(Level::INFO, ?default, "creating table guilds");
 		let mut action: CreateTableAction<GuildSettings> = Action::new();
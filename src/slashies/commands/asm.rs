@@ -0,0 +1,147 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+
+use super::play::{code_option, mode_option, resolve_code};
+use crate::{
+	helpers::{
+		parsing::{CodeInput, CommandParse},
+		playground::{
+			filter_functions, AssemblyFlavor, BuildMode, CompileTarget, Edition,
+			PlaygroundCompileRequest, PlaygroundOutput, PlaygroundRequest, RustChannel,
+		},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Asm {
+	code: CodeInput,
+	target: CompileTarget,
+	flavor: AssemblyFlavor,
+	mode: BuildMode,
+}
+
+impl Asm {
+	const fn output_info(&self) -> (&'static str, &'static str) {
+		match self.target {
+			CompileTarget::Assembly => ("x86asm", "output.s"),
+			CompileTarget::LlvmIr => ("llvm", "output.ll"),
+			CompileTarget::Mir => ("rust", "output.mir"),
+			CompileTarget::Hir => ("rust", "output.rs"),
+			CompileTarget::Wasm => ("wasm", "output.wat"),
+		}
+	}
+}
+
+impl SlashCommand for Asm {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			let code = match resolve_code(&helper, &mut responder, &self.code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let request = PlaygroundCompileRequest::new(
+				PlaygroundRequest::new(
+					&code,
+					RustChannel::default(),
+					Edition::default(),
+					self.mode,
+					false,
+				),
+				self.target,
+			)
+			.assembly_flavor(self.flavor);
+
+			let mut response = helper.playground().compile(&request).await?;
+
+			if response.success {
+				let (language, filename) = self.output_info();
+				let output = filter_functions(&response.code, &code, self.target);
+
+				responder.code_block(output, language, filename);
+			} else {
+				response.format();
+
+				PlaygroundOutput::stderr(&response.stderr).apply(&mut responder);
+			}
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Asm {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"asm".to_owned(),
+			"Show the assembly, LLVM IR, MIR, HIR or wasm generated for some code".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to compile"))
+		.option(
+			StringBuilder::new(
+				"target".to_owned(),
+				"The output to show (defaults to assembly)".to_owned(),
+			)
+			.choices([
+				("assembly".to_owned(), CompileTarget::Assembly.to_string()),
+				("LLVM IR".to_owned(), CompileTarget::LlvmIr.to_string()),
+				("MIR".to_owned(), CompileTarget::Mir.to_string()),
+				("HIR".to_owned(), CompileTarget::Hir.to_string()),
+				("wasm".to_owned(), CompileTarget::Wasm.to_string()),
+			]),
+		)
+		.option(
+			StringBuilder::new(
+				"flavor".to_owned(),
+				"The assembly flavor to use (defaults to AT&T)".to_owned(),
+			)
+			.choices([
+				("AT&T".to_owned(), AssemblyFlavor::Att.to_string()),
+				("Intel".to_owned(), AssemblyFlavor::Intel.to_string()),
+			]),
+		)
+		.option(mode_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut asm = Self {
+			code: CodeInput::Missing,
+			target: CompileTarget::default(),
+			flavor: AssemblyFlavor::default(),
+			mode: BuildMode::default(),
+		};
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"target" => asm.target = option.value.parse_option().unwrap_or_default(),
+				"flavor" => asm.flavor = option.value.parse_option().unwrap_or_default(),
+				"mode" => asm.mode = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		asm.code = CodeInput::new(code);
+
+		Ok(asm)
+	}
+}
@@ -0,0 +1,87 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{code_option, edition_option, resolve_code};
+use crate::{
+	helpers::{
+		parsing::{CodeInput, CommandParse},
+		playground::{ClippyRequest, Edition, PlaygroundOutput},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Clippy {
+	code: CodeInput,
+	edition: Edition,
+}
+
+impl SlashCommand for Clippy {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			let code = match resolve_code(&helper, &mut responder, &self.code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let request = ClippyRequest::new(&code, self.edition);
+
+			let mut response = helper.playground().clippy(&request).await?;
+
+			response.format();
+
+			if response.stderr.trim().is_empty() {
+				responder.message("no lints found".to_owned());
+			} else {
+				PlaygroundOutput::stderr(&response.stderr).apply(&mut responder);
+			}
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Clippy {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"clippy".to_owned(),
+			"Lint code with clippy".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to lint"))
+		.option(edition_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut edition = Edition::default();
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"edition" => edition = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		let code = CodeInput::new(code);
+
+		Ok(Self { code, edition })
+	}
+}
@@ -96,11 +96,11 @@ impl Crate {
 		output
 	}
 
-	async fn get_crate(&self, reqwest_client: &Client) -> Result<CrateResult> {
+	async fn get_crate(&self, reqwest_client: &Client, api_url: &str) -> Result<CrateResult> {
 		event!(Level::INFO, "searching for crate `{}`", &self.crate_name);
 
 		let crate_list = reqwest_client
-			.get("https://crates.io/api/v1/crates")
+			.get(format!("{}/crates", api_url))
 			.header(header::USER_AGENT, USER_AGENT)
 			.query(&[("q", self.crate_name.as_str())])
 			.send()
@@ -146,7 +146,7 @@ impl SlashCommand for Crate {
 
 			let cdn = helper.cdn();
 
-			let krate = self.get_crate(cdn).await?;
+			let krate = self.get_crate(cdn, &helper.config().crates_io_url).await?;
 
 			match krate {
 				CrateResult::NotFound(msg) => {
@@ -191,7 +191,7 @@ impl SlashCommand for Crate {
 			let client = helper.cdn();
 
 			let response = client
-				.get("https://crates.io/api/v1/crates")
+				.get(format!("{}/crates", helper.config().crates_io_url))
 				.header(header::USER_AGENT, USER_AGENT)
 				.query(&[
 					("q", self.crate_name.as_str()),
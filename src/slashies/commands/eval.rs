@@ -0,0 +1,138 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+
+use super::play::{channel_option, edition_option, fresh_option, mode_option};
+use crate::{
+	helpers::{
+		parsing::{CodeBlock, CommandParse},
+		playground::{
+			BuildMode, Edition, PlaygroundRequest, PlaygroundResponse, ResultHandling, RustChannel,
+		},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Eval {
+	expression: String,
+	channel: RustChannel,
+	edition: Edition,
+	mode: BuildMode,
+	fresh: bool,
+}
+
+impl Eval {
+	fn compact(response: &PlaygroundResponse) -> String {
+		let output = if response.success {
+			response.stdout.trim()
+		} else {
+			response
+				.stderr
+				.lines()
+				.find(|line| line.starts_with("error") || line.starts_with("thread '"))
+				.unwrap_or_else(|| response.stderr.trim())
+		};
+
+		if output.is_empty() {
+			"no output".to_owned()
+		} else if output.contains('\n') || output.contains('`') {
+			CodeBlock::new(output, None).to_string()
+		} else {
+			format!("`{}`", output)
+		}
+	}
+}
+
+impl SlashCommand for Eval {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			if helper
+				.respond_if_rate_limited(&mut responder)
+				.await
+				.into_diagnostic()?
+			{
+				return Ok(());
+			}
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let code = ResultHandling::Print.apply(&self.expression);
+			let request =
+				PlaygroundRequest::new(&code, self.channel, self.edition, self.mode, false);
+
+			let mut response = if self.fresh {
+				helper.playground().execute_uncached(&request).await?
+			} else {
+				helper.playground().execute(&request).await?
+			};
+
+			response.format(false);
+
+			responder.message(Self::compact(&response));
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Eval {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"eval".to_owned(),
+			"Evaluate an expression and print its Debug output".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(
+			StringBuilder::new(
+				"expression".to_owned(),
+				"The expression to evaluate".to_owned(),
+			)
+			.required(true),
+		)
+		.option(channel_option())
+		.option(edition_option())
+		.option(mode_option())
+		.option(fresh_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut expression: Option<String> = None;
+		let mut eval = Self {
+			expression: String::new(),
+			channel: RustChannel::default(),
+			edition: Edition::default(),
+			mode: BuildMode::default(),
+			fresh: false,
+		};
+
+		for option in data.options {
+			match option.name.as_str() {
+				"expression" => expression = option.value.parse_option(),
+				"channel" => eval.channel = option.value.parse_option().unwrap_or_default(),
+				"edition" => eval.edition = option.value.parse_option().unwrap_or_default(),
+				"mode" => eval.mode = option.value.parse_option().unwrap_or_default(),
+				"fresh" => eval.fresh = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		eval.expression =
+			CodeBlock::strip(&expression.ok_or_else(|| error!("Failed to find option"))?);
+
+		Ok(eval)
+	}
+}
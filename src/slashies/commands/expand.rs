@@ -0,0 +1,105 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{code_option, edition_option, resolve_code, use_ansi};
+use crate::{
+	helpers::{
+		parsing::{CodeInput, CommandParse},
+		playground::{
+			BuildMode, Edition, MacroExpansionRequest, PlaygroundOutput, PlaygroundRequest,
+			RustChannel,
+		},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Expand {
+	code: CodeInput,
+	edition: Edition,
+}
+
+impl SlashCommand for Expand {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			let code = match resolve_code(&helper, &mut responder, &self.code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let request = MacroExpansionRequest {
+				edition: self.edition,
+				code: &code,
+			};
+
+			let mut response = helper.playground().macro_expansion(&request).await?;
+
+			if response.success {
+				responder.code_block(response.stdout, "rust", "expanded.rs");
+			} else {
+				response.format(true);
+
+				let share_request = PlaygroundRequest::new(
+					&code,
+					RustChannel::Nightly,
+					self.edition,
+					BuildMode::Debug,
+					false,
+				);
+
+				let ansi = use_ansi(&helper, &responder, None).await;
+
+				PlaygroundOutput::new(helper.playground(), &response, &share_request, ansi)
+					.await
+					.apply(&mut responder);
+			}
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Expand {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"expand".to_owned(),
+			"Expand the macros in a piece of code".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to expand"))
+		.option(edition_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut edition = Edition::default();
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"edition" => edition = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		let code = CodeInput::new(code);
+
+		Ok(Self { code, edition })
+	}
+}
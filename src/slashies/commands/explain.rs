@@ -0,0 +1,69 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+
+use crate::{
+	helpers::{error_codes, parsing::CommandParse, InteractionsHelper},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Explain {
+	code: String,
+}
+
+impl SlashCommand for Explain {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			if let Some((code, explanation)) = error_codes::explain(&self.code) {
+				responder.embed(error_codes::embed(code, explanation)?);
+			} else {
+				responder
+					.message(format!("couldn't find error code `{}`", self.code))
+					.ephemeral();
+			}
+
+			helper.respond(&mut responder).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Explain {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"explain".to_owned(),
+			"Show the explanation for a compiler error code".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(
+			StringBuilder::new(
+				"code".to_owned(),
+				"The error code to explain, such as E0382".to_owned(),
+			)
+			.required(true),
+		)
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let code = data
+			.options
+			.into_iter()
+			.find(|option| option.name == "code")
+			.and_then(|option| option.value.parse_option())
+			.ok_or_else(|| error!("Failed to find option"))?;
+
+		Ok(Self { code })
+	}
+}
@@ -0,0 +1,144 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{code_option, edition_option, resolve_code};
+use crate::{
+	helpers::{
+		parsing::{target_message, CodeBlockError, CodeInput, CommandParse},
+		playground::{Edition, FormatRequest, PlaygroundOutput},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+	utils::DefaultMessages,
+};
+
+async fn run_format(
+	helper: InteractionsHelper,
+	mut responder: SlashData,
+	code: &CodeInput,
+	edition: Edition,
+) -> Result<()> {
+	let code = match resolve_code(&helper, &mut responder, code).await? {
+		Some(code) => code,
+		None => return Ok(()),
+	};
+
+	helper.ack(&responder).await.into_diagnostic()?;
+
+	let request = FormatRequest {
+		edition,
+		code: &code,
+	};
+
+	let response = helper.playground().format(&request).await?;
+
+	if response.success {
+		responder.code_block(response.code, "rust", "formatted.rs");
+	} else if response.stderr.trim().is_empty() {
+		responder.message("rustfmt failed without any output".to_owned());
+	} else {
+		PlaygroundOutput::stderr(&response.stderr).apply(&mut responder);
+	}
+
+	helper.update(&mut responder).await?;
+
+	Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct Fmt {
+	code: CodeInput,
+	edition: Edition,
+}
+
+impl SlashCommand for Fmt {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(run_format(helper, responder, &self.code, self.edition))
+	}
+}
+
+impl DefineCommand for Fmt {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"fmt".to_owned(),
+			"Format code with rustfmt".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to format"))
+		.option(edition_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut edition = Edition::default();
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"edition" => edition = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		let code = CodeInput::new(code);
+
+		Ok(Self { code, edition })
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatCode {
+	code: Result<CodeInput, CodeBlockError>,
+}
+
+impl SlashCommand for FormatCode {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			match &self.code {
+				Ok(code) => run_format(helper, responder, code, Edition::default()).await,
+				Err(e) => {
+					responder
+						.message(DefaultMessages::from(*e).to_string())
+						.ephemeral();
+
+					helper.respond(&mut responder).await.into_diagnostic()
+				}
+			}
+		})
+	}
+}
+
+impl DefineCommand for FormatCode {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"Format code".to_owned(),
+			String::new(),
+			CommandType::Message,
+		)
+		.default_permission(true)
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let message = target_message(data)
+			.ok_or_else(|| error!("failed to get target message (this shouldn't happen)"))?;
+
+		Ok(Self {
+			code: CodeInput::from_message(&message),
+		})
+	}
+}
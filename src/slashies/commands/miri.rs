@@ -0,0 +1,110 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{ansi_option, code_option, edition_option, resolve_code, use_ansi};
+use crate::{
+	helpers::{
+		parsing::{CodeInput, CommandParse},
+		playground::{
+			BuildMode, Edition, MiriRequest, PlaygroundOutput, PlaygroundRequest, ResultHandling,
+			RustChannel,
+		},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone)]
+pub struct Miri {
+	code: CodeInput,
+	edition: Edition,
+	ansi: Option<bool>,
+}
+
+impl SlashCommand for Miri {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			let code = match resolve_code(&helper, &mut responder, &self.code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let code = ResultHandling::None.apply(&code);
+			let request = MiriRequest {
+				edition: self.edition,
+				code: &code,
+			};
+
+			let mut response = helper.playground().miri(&request).await?;
+
+			response.format(false);
+
+			let share_request = PlaygroundRequest::new(
+				&code,
+				RustChannel::Nightly,
+				self.edition,
+				BuildMode::Debug,
+				false,
+			);
+
+			let ansi = use_ansi(&helper, &responder, self.ansi).await;
+
+			PlaygroundOutput::new(helper.playground(), &response, &share_request, ansi)
+				.await
+				.apply(&mut responder);
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Miri {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"miri".to_owned(),
+			"Run code under Miri to check for undefined behavior".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to check"))
+		.option(edition_option())
+		.option(ansi_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut edition = Edition::default();
+		let mut ansi = None;
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"edition" => edition = option.value.parse_option().unwrap_or_default(),
+				"ansi" => ansi = option.value.parse_option(),
+				_ => {}
+			}
+		}
+
+		let code = CodeInput::new(code);
+
+		Ok(Self {
+			code,
+			edition,
+			ansi,
+		})
+	}
+}
@@ -1,6 +1,34 @@
+mod asm;
+mod clippy;
+mod eval;
+mod expand;
+mod explain;
+mod fmt;
 #[path = "crate.rs"]
 mod krate;
+mod miri;
 mod ping;
+mod play;
+mod run;
+mod settings;
+mod status;
 mod tag;
+mod test;
 
-pub use self::{krate::Crate, ping::Ping, tag::Tag};
+pub use self::{
+	asm::Asm,
+	clippy::Clippy,
+	eval::Eval,
+	expand::Expand,
+	explain::Explain,
+	fmt::{Fmt, FormatCode},
+	krate::Crate,
+	miri::Miri,
+	ping::Ping,
+	play::Play,
+	run::RunCode,
+	settings::Settings,
+	status::Status,
+	tag::Tag,
+	test::Test,
+};
@@ -1,4 +1,4 @@
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
 use futures_util::Future;
 use twilight_model::application::{
@@ -12,6 +12,83 @@ use crate::{
 	slashies::{DefineCommand, SlashCommand, SlashData},
 };
 
+const MAX_LISTED_SHARDS: usize = 10;
+const SLOWEST_SHARDS: usize = 5;
+
+pub(super) fn shard_latencies(helper: &InteractionsHelper) -> Vec<(u64, Option<Duration>)> {
+	let mut shards = helper
+		.cluster()
+		.info()
+		.into_iter()
+		.map(|(id, info)| (id, info.latency().average()))
+		.collect::<Vec<_>>();
+
+	shards.sort_unstable_by_key(|(id, _)| *id);
+
+	shards
+}
+
+pub(super) fn latency_report(shards: &[(u64, Option<Duration>)]) -> String {
+	if shards.len() <= MAX_LISTED_SHARDS {
+		return shards
+			.iter()
+			.map(|(id, latency)| match latency {
+				Some(latency) => format!(
+					"shard {}: average latency is {} milliseconds",
+					id,
+					latency.as_millis()
+				),
+				None => format!("shard {}: couldn't quite get average latency", id),
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+	}
+
+	let mut measured = shards
+		.iter()
+		.filter_map(|(id, latency)| latency.map(|latency| (*id, latency)))
+		.collect::<Vec<_>>();
+
+	let mut report = format!("{} shards", shards.len());
+
+	if measured.len() < shards.len() {
+		let _ = write!(
+			report,
+			", {} without an average latency yet",
+			shards.len() - measured.len()
+		);
+	}
+
+	if measured.is_empty() {
+		return report;
+	}
+
+	measured.sort_unstable_by_key(|(_, latency)| *latency);
+
+	let total = measured
+		.iter()
+		.map(|(_, latency)| *latency)
+		.sum::<Duration>();
+	let average = total / u32::try_from(measured.len()).unwrap_or(u32::MAX);
+
+	let _ = write!(
+		report,
+		"\nlatency: {} min, {} average, {} max (milliseconds)\nslowest: {}",
+		measured[0].1.as_millis(),
+		average.as_millis(),
+		measured[measured.len() - 1].1.as_millis(),
+		measured
+			.iter()
+			.rev()
+			.take(SLOWEST_SHARDS)
+			.map(|(id, latency)| format!("shard {} ({})", id, latency.as_millis()))
+			.collect::<Vec<_>>()
+			.join(", ")
+	);
+
+	report
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Ping;
 
@@ -23,22 +100,10 @@ impl SlashCommand for Ping {
 	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
 		Box::pin(async move {
 			data.ephemeral();
-			let context = helper.context();
-
-			if let Some(pong) = context
-				.shard()
-				.info()
-				.into_diagnostic()?
-				.latency()
-				.average()
-			{
-				data.message(format!(
-					"Pong! Average latency is {} milliseconds",
-					pong.as_millis()
-				));
-			} else {
-				data.message("Pong! Couldn't quite get average latency".to_owned());
-			}
+
+			let message = format!("Pong!\n{}", latency_report(&shard_latencies(&helper)));
+
+			data.message(message);
 
 			helper.respond(&mut data).await.into_diagnostic()?;
 
@@ -61,3 +126,37 @@ impl DefineCommand for Ping {
 		Ok(Self)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::latency_report;
+
+	#[test]
+	fn summarizes_many_shards() {
+		let shards = (0..1000)
+			.map(|id| (id, (id % 7 != 0).then(|| Duration::from_millis(id))))
+			.collect::<Vec<_>>();
+
+		let report = latency_report(&shards);
+
+		assert!(report.len() < 2000);
+		assert!(report.starts_with("1000 shards, 143 without an average latency yet"));
+		assert!(report.contains("1 min"));
+		assert!(report.contains("999 max"));
+		assert!(report.ends_with(
+			"shard 999 (999), shard 998 (998), shard 997 (997), shard 996 (996), shard 995 (995)"
+		));
+	}
+
+	#[test]
+	fn lists_few_shards() {
+		let report = latency_report(&[(0, Some(Duration::from_millis(42))), (1, None)]);
+
+		assert_eq!(
+			report,
+			"shard 0: average latency is 42 milliseconds\nshard 1: couldn't quite get average latency"
+		);
+	}
+}
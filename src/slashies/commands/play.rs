@@ -0,0 +1,421 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType,
+	interaction::application_command::{CommandData, CommandDataOption, CommandOptionValue},
+};
+use twilight_util::builder::command::{
+	BooleanBuilder, CommandBuilder, StringBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	helpers::{
+		parsing::{CodeInput, CommandParse},
+		playground::{
+			BuildMode, Edition, PlaygroundComparison, PlaygroundLink, PlaygroundRequest,
+			PlaygroundSession, ResultHandling, RustChannel,
+		},
+		InteractionsHelper,
+	},
+	prelude::*,
+	settings::{GuildSettings, Tables},
+	slashies::{components::respond_with_session, DefineCommand, SlashCommand, SlashData},
+};
+
+pub(super) fn code_option(description: &str) -> StringBuilder {
+	StringBuilder::new("code".to_owned(), description.to_owned()).required(true)
+}
+
+pub(super) async fn resolve_code(
+	helper: &InteractionsHelper,
+	responder: &mut SlashData,
+	code: &CodeInput,
+) -> Result<Option<String>> {
+	let code = match code.resolve(helper.cdn()).await {
+		Ok(code) => code,
+		Err(e) => {
+			responder.message(e.to_string()).ephemeral();
+			helper.respond(responder).await.into_diagnostic()?;
+
+			return Ok(None);
+		}
+	};
+
+	if helper
+		.respond_if_rate_limited(responder)
+		.await
+		.into_diagnostic()?
+	{
+		return Ok(None);
+	}
+
+	Ok(Some(code))
+}
+
+pub(super) fn channel_option() -> StringBuilder {
+	StringBuilder::new(
+		"channel".to_owned(),
+		"The Rust channel to use (defaults to nightly)".to_owned(),
+	)
+	.choices(
+		[RustChannel::Stable, RustChannel::Beta, RustChannel::Nightly]
+			.map(|channel| (channel.to_string(), channel.to_string())),
+	)
+}
+
+pub(super) fn edition_option() -> StringBuilder {
+	StringBuilder::new(
+		"edition".to_owned(),
+		"The Rust edition to use (defaults to 2018)".to_owned(),
+	)
+	.choices(
+		[Edition::E2015, Edition::E2018, Edition::E2021]
+			.map(|edition| (edition.to_string(), edition.to_string())),
+	)
+}
+
+pub(super) fn mode_option() -> StringBuilder {
+	StringBuilder::new(
+		"mode".to_owned(),
+		"The build mode to use (defaults to debug)".to_owned(),
+	)
+	.choices(
+		[BuildMode::Debug, BuildMode::Release].map(|mode| (mode.to_string(), mode.to_string())),
+	)
+}
+
+pub(super) fn warnings_option() -> BooleanBuilder {
+	BooleanBuilder::new(
+		"warnings".to_owned(),
+		"Whether to show compiler warnings (defaults to false)".to_owned(),
+	)
+}
+
+pub(super) fn fresh_option() -> BooleanBuilder {
+	BooleanBuilder::new(
+		"fresh".to_owned(),
+		"Whether to skip recently cached results (defaults to false)".to_owned(),
+	)
+}
+
+pub(super) fn ansi_option() -> BooleanBuilder {
+	BooleanBuilder::new(
+		"ansi".to_owned(),
+		"Whether to color compiler output (defaults to the server setting)".to_owned(),
+	)
+}
+
+pub(super) async fn use_ansi(
+	helper: &InteractionsHelper,
+	responder: &SlashData,
+	ansi: Option<bool>,
+) -> bool {
+	if let Some(ansi) = ansi {
+		return ansi;
+	}
+
+	let guild_id = match responder.guild_id {
+		Some(guild_id) => guild_id,
+		None => return false,
+	};
+
+	Tables::Guilds
+		.get_entry::<GuildSettings>(helper.database(), &guild_id)
+		.await
+		.map_or(false, |settings| settings.ansi_output())
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayOptions {
+	code: CodeInput,
+	channel: RustChannel,
+	edition: Edition,
+	mode: BuildMode,
+	warnings: bool,
+	ansi: Option<bool>,
+	fresh: bool,
+}
+
+impl PlayOptions {
+	fn parse(options: Vec<CommandDataOption>) -> Self {
+		let mut code: Option<String> = None;
+		let mut play = Self {
+			code: CodeInput::Missing,
+			channel: RustChannel::default(),
+			edition: Edition::default(),
+			mode: BuildMode::default(),
+			warnings: false,
+			ansi: None,
+			fresh: false,
+		};
+
+		for option in options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"channel" => play.channel = option.value.parse_option().unwrap_or_default(),
+				"edition" => play.edition = option.value.parse_option().unwrap_or_default(),
+				"mode" => play.mode = option.value.parse_option().unwrap_or_default(),
+				"warnings" => play.warnings = option.value.parse_option().unwrap_or_default(),
+				"ansi" => play.ansi = option.value.parse_option(),
+				"fresh" => play.fresh = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		play.code = CodeInput::new(code);
+
+		play
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkOptions {
+	link: String,
+	warnings: bool,
+	ansi: Option<bool>,
+	fresh: bool,
+}
+
+impl LinkOptions {
+	fn parse(options: Vec<CommandDataOption>) -> Result<Self> {
+		let mut link: Option<String> = None;
+		let mut warnings = false;
+		let mut ansi = None;
+		let mut fresh = false;
+
+		for option in options {
+			match option.name.as_str() {
+				"link" => link = option.value.parse_option(),
+				"warnings" => warnings = option.value.parse_option().unwrap_or_default(),
+				"ansi" => ansi = option.value.parse_option(),
+				"fresh" => fresh = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		Ok(Self {
+			link: link.ok_or_else(|| error!("Failed to find option"))?,
+			warnings,
+			ansi,
+			fresh,
+		})
+	}
+}
+
+#[derive(Debug, Clone)]
+pub enum Play {
+	Run(PlayOptions),
+	Compare(PlayOptions),
+	Link(LinkOptions),
+}
+
+impl Play {
+	async fn run_run(
+		options: &PlayOptions,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Result<()> {
+		let code = match resolve_code(&helper, &mut responder, &options.code).await? {
+			Some(code) => code,
+			None => return Ok(()),
+		};
+
+		helper.ack(&responder).await.into_diagnostic()?;
+
+		let session = PlaygroundSession {
+			code: ResultHandling::None.apply(&code).into_owned(),
+			channel: options.channel,
+			edition: options.edition,
+			mode: options.mode,
+			warnings: options.warnings,
+			author: responder.user_id(),
+			ephemeral: false,
+			ansi: use_ansi(&helper, &responder, options.ansi).await,
+			fresh: options.fresh,
+		};
+
+		respond_with_session(&helper, responder, session).await
+	}
+
+	async fn run_compare(
+		options: &PlayOptions,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Result<()> {
+		let code = match resolve_code(&helper, &mut responder, &options.code).await? {
+			Some(code) => code,
+			None => return Ok(()),
+		};
+
+		helper.ack(&responder).await.into_diagnostic()?;
+
+		let code = ResultHandling::None.apply(&code);
+		let request = PlaygroundRequest::new(
+			&code,
+			RustChannel::default(),
+			options.edition,
+			options.mode,
+			false,
+		);
+
+		let comparison = PlaygroundComparison::run(
+			helper.playground(),
+			request,
+			options.warnings,
+			options.fresh,
+		)
+		.await?;
+
+		let ansi = use_ansi(&helper, &responder, options.ansi).await;
+
+		responder.embed(comparison.embed(ansi)?);
+
+		helper.update(&mut responder).await?;
+
+		Ok(())
+	}
+
+	async fn run_link(
+		options: &LinkOptions,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Result<()> {
+		let link = if let Some(link) =
+			PlaygroundLink::parse(&options.link, &helper.playground().base_url())
+		{
+			link
+		} else {
+			responder
+				.message("that isn't a playground link or gist ID".to_owned())
+				.ephemeral();
+			helper.respond(&mut responder).await.into_diagnostic()?;
+			return Ok(());
+		};
+
+		if helper
+			.respond_if_rate_limited(&mut responder)
+			.await
+			.into_diagnostic()?
+		{
+			return Ok(());
+		}
+
+		helper.ack(&responder).await.into_diagnostic()?;
+
+		let code = match helper.playground().fetch_gist(&link.gist).await {
+			Ok(code) => code,
+			Err(e) => {
+				event!(Level::WARN, "failed to fetch gist {}: {:?}", link.gist, e);
+				responder.message(format!("couldn't find a gist with the ID `{}`", link.gist));
+				helper.update(&mut responder).await?;
+				return Ok(());
+			}
+		};
+
+		let session = PlaygroundSession {
+			code,
+			channel: link.channel.unwrap_or_default(),
+			edition: link.edition.unwrap_or_default(),
+			mode: link.mode.unwrap_or_default(),
+			warnings: options.warnings,
+			author: responder.user_id(),
+			ephemeral: false,
+			ansi: use_ansi(&helper, &responder, options.ansi).await,
+			fresh: options.fresh,
+		};
+
+		respond_with_session(&helper, responder, session).await
+	}
+}
+
+impl SlashCommand for Play {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			match self {
+				Self::Run(options) => Self::run_run(options, helper, responder).await,
+				Self::Compare(options) => Self::run_compare(options, helper, responder).await,
+				Self::Link(options) => Self::run_link(options, helper, responder).await,
+			}
+		})
+	}
+}
+
+impl DefineCommand for Play {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"play".to_owned(),
+			"Run code on the Rust playground".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		// discord doesn't allow top level options next to subcommands, so the old flat
+		// `/play code:` form lives on as `/play run`
+		.option(
+			SubCommandBuilder::new(
+				"run".to_owned(),
+				"Run code on the playground (what `/play` used to do)".to_owned(),
+			)
+			.option(code_option("The code to run"))
+			.option(channel_option())
+			.option(edition_option())
+			.option(mode_option())
+			.option(warnings_option())
+			.option(ansi_option())
+			.option(fresh_option()),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"compare".to_owned(),
+				"Run code on stable, beta and nightly and compare the results".to_owned(),
+			)
+			.option(code_option("The code to run"))
+			.option(edition_option())
+			.option(mode_option())
+			.option(warnings_option())
+			.option(ansi_option())
+			.option(fresh_option()),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"link".to_owned(),
+				"Run the code from a playground share link or gist ID".to_owned(),
+			)
+			.option(
+				StringBuilder::new(
+					"link".to_owned(),
+					"The playground link or gist ID to run".to_owned(),
+				)
+				.required(true),
+			)
+			.option(warnings_option())
+			.option(ansi_option())
+			.option(fresh_option()),
+		)
+	}
+
+	fn parse(mut data: CommandData) -> Result<Self> {
+		if data.options.len() != 1 {
+			return Err(error!(
+				"more than one subcommand was received (this shouldn't happen)"
+			));
+		}
+		let subcommand_value = data
+			.options
+			.pop()
+			.ok_or_else(|| error!("failed to get subcommand value (this shouldn't happen)"))?;
+		match subcommand_value.value {
+			CommandOptionValue::SubCommand(v) => match subcommand_value.name.as_str() {
+				"run" => Ok(Self::Run(PlayOptions::parse(v))),
+				"compare" => Ok(Self::Compare(PlayOptions::parse(v))),
+				"link" => Ok(Self::Link(LinkOptions::parse(v)?)),
+				_ => Err(error!("invalid subcommand variant")),
+			},
+			_ => Err(error!("invalid subcommand value option")),
+		}
+	}
+}
@@ -0,0 +1,84 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{resolve_code, use_ansi};
+use crate::{
+	helpers::{
+		parsing::{target_message, CodeBlockError, CodeInput},
+		playground::{BuildMode, Edition, PlaygroundSession, ResultHandling, RustChannel},
+		InteractionsHelper,
+	},
+	prelude::*,
+	slashies::{components::respond_with_session, DefineCommand, SlashCommand, SlashData},
+	utils::DefaultMessages,
+};
+
+#[derive(Debug, Clone)]
+pub struct RunCode {
+	code: Result<CodeInput, CodeBlockError>,
+}
+
+impl SlashCommand for RunCode {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			let code = match &self.code {
+				Ok(code) => code,
+				Err(e) => {
+					responder.message(DefaultMessages::from(*e).to_string());
+					helper.respond(&mut responder).await.into_diagnostic()?;
+					return Ok(());
+				}
+			};
+
+			let code = match resolve_code(&helper, &mut responder, code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let session = PlaygroundSession {
+				code: ResultHandling::None.apply(&code).into_owned(),
+				channel: RustChannel::default(),
+				edition: Edition::default(),
+				mode: BuildMode::default(),
+				warnings: false,
+				author: responder.user_id(),
+				ephemeral: true,
+				fresh: false,
+				ansi: use_ansi(&helper, &responder, None).await,
+			};
+
+			respond_with_session(&helper, responder, session).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for RunCode {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new("Run code".to_owned(), String::new(), CommandType::Message)
+			.default_permission(true)
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let message = target_message(data)
+			.ok_or_else(|| error!("failed to get target message (this shouldn't happen)"))?;
+
+		Ok(Self {
+			code: CodeInput::from_message(&message),
+		})
+	}
+}
@@ -0,0 +1,258 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_gateway::Intents;
+use twilight_model::{
+	application::{
+		command::CommandType,
+		interaction::application_command::{CommandData, CommandDataOption, CommandOptionValue},
+	},
+	guild::Permissions,
+};
+use twilight_util::builder::command::{
+	BooleanBuilder, CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	helpers::{
+		parsing::CommandParse,
+		rate_limit::{RateLimit, RateLimits},
+		InteractionsHelper,
+	},
+	prelude::*,
+	settings::{GuildSettings, Tables},
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitScope {
+	User,
+	Server,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Settings {
+	Ansi {
+		enabled: bool,
+	},
+	RateLimit {
+		scope: RateLimitScope,
+		requests: Option<i64>,
+		seconds: Option<i64>,
+	},
+}
+
+impl Settings {
+	fn parse_enabled(data: &[CommandDataOption]) -> bool {
+		data.iter()
+			.find(|opt| opt.name == "enabled")
+			.cloned()
+			.and_then(|opt| opt.value.parse_option())
+			.unwrap_or_default()
+	}
+
+	fn parse_rate_limit(data: Vec<CommandDataOption>) -> Self {
+		let mut scope = RateLimitScope::User;
+		let mut requests = None;
+		let mut seconds = None;
+
+		for option in data {
+			match option.name.as_str() {
+				"scope" => {
+					if CommandParse::<String>::parse_option(option.value).as_deref()
+						== Some("server")
+					{
+						scope = RateLimitScope::Server;
+					}
+				}
+				"requests" => requests = option.value.parse_option(),
+				"seconds" => seconds = option.value.parse_option(),
+				_ => {}
+			}
+		}
+
+		Self::RateLimit {
+			scope,
+			requests,
+			seconds,
+		}
+	}
+
+	fn apply(self, guild_settings: &mut GuildSettings, defaults: RateLimits) -> String {
+		match self {
+			Self::Ansi { enabled } => {
+				guild_settings.set_ansi_output(enabled);
+
+				format!(
+					"colored compiler output is now {}",
+					if enabled { "enabled" } else { "disabled" }
+				)
+			}
+			Self::RateLimit {
+				scope,
+				requests,
+				seconds,
+			} => {
+				let rate_limit = match (requests, seconds) {
+					(None, None) => None,
+					(Some(requests), Some(seconds)) => {
+						let rate_limit = u32::try_from(requests)
+							.ok()
+							.zip(u64::try_from(seconds).ok())
+							.and_then(|(requests, seconds)| RateLimit::new(requests, seconds));
+
+						if rate_limit.is_none() {
+							return "requests and seconds must both be at least 1".to_owned();
+						}
+
+						rate_limit
+					}
+					_ => {
+						return "set both requests and seconds, or neither to reset the limit"
+							.to_owned()
+					}
+				};
+
+				match scope {
+					RateLimitScope::User => guild_settings.set_user_rate_limit(rate_limit),
+					RateLimitScope::Server => guild_settings.set_guild_rate_limit(rate_limit),
+				}
+
+				let limits = guild_settings.rate_limits(defaults);
+				let (name, limit) = match scope {
+					RateLimitScope::User => ("each user", limits.user),
+					RateLimitScope::Server => ("this server", limits.guild),
+				};
+
+				format!(
+					"{} can now make {} playground requests every {} seconds",
+					name,
+					limit.requests(),
+					limit.seconds()
+				)
+			}
+		}
+	}
+}
+
+impl SlashCommand for Settings {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			let guild_id = if let Some(guild_id) = responder.guild_id {
+				guild_id
+			} else {
+				responder.message("settings can only be changed in a server".to_owned());
+				helper.respond(&mut responder).await.into_diagnostic()?;
+				return Ok(());
+			};
+
+			let can_manage_guild = {
+				let user_permissions = responder.user_permissions(&helper)?;
+
+				user_permissions.contains(Permissions::ADMINISTRATOR)
+					|| user_permissions.contains(Permissions::MANAGE_GUILD)
+			};
+
+			if !can_manage_guild {
+				responder.message("you need the manage server permission to do this".to_owned());
+				helper.respond(&mut responder).await.into_diagnostic()?;
+				return Ok(());
+			}
+
+			let mut guild_settings = Tables::Guilds
+				.get_entry::<GuildSettings>(helper.database(), &guild_id)
+				.await?;
+
+			let message = self.apply(&mut guild_settings, helper.config().rate_limits);
+
+			Tables::Guilds
+				.update_entry(helper.database(), &guild_settings)
+				.await?;
+
+			responder.message(message);
+			helper.respond(&mut responder).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Settings {
+	const INTENTS: Intents = Intents::GUILDS.union(Intents::GUILD_MEMBERS);
+
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"settings".to_owned(),
+			"Change the bot's settings for this server".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(
+			SubCommandBuilder::new(
+				"ansi".to_owned(),
+				"Color compiler output by default".to_owned(),
+			)
+			.option(
+				BooleanBuilder::new(
+					"enabled".to_owned(),
+					"Whether compiler output should be colored".to_owned(),
+				)
+				.required(true),
+			),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"rate-limit".to_owned(),
+				"Limit how often the playground can be used, leave out both limits to reset"
+					.to_owned(),
+			)
+			.option(
+				StringBuilder::new(
+					"scope".to_owned(),
+					"Whether the limit applies to each user or the whole server".to_owned(),
+				)
+				.required(true)
+				.choices([
+					("user".to_owned(), "user".to_owned()),
+					("server".to_owned(), "server".to_owned()),
+				]),
+			)
+			.option(IntegerBuilder::new(
+				"requests".to_owned(),
+				"How many requests can be made in the time period".to_owned(),
+			))
+			.option(IntegerBuilder::new(
+				"seconds".to_owned(),
+				"The length of the time period in seconds".to_owned(),
+			)),
+		)
+	}
+
+	fn parse(mut data: CommandData) -> Result<Self> {
+		if data.options.len() != 1 {
+			return Err(error!(
+				"more than one subcommand was received (this shouldn't happen)"
+			));
+		}
+		let subcommand_value = data
+			.options
+			.pop()
+			.ok_or_else(|| error!("failed to get subcommand value (this shouldn't happen)"))?;
+		match subcommand_value.value {
+			CommandOptionValue::SubCommand(v) => match subcommand_value.name.as_str() {
+				"ansi" => Ok(Self::Ansi {
+					enabled: Self::parse_enabled(&v),
+				}),
+				"rate-limit" => Ok(Self::parse_rate_limit(v)),
+				_ => Err(error!("invalid subcommand variant")),
+			},
+			_ => Err(error!("invalid subcommand value option")),
+		}
+	}
+}
@@ -0,0 +1,68 @@
+use std::{collections::BTreeMap, pin::Pin};
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::ping::{latency_report, shard_latencies};
+use crate::{
+	helpers::InteractionsHelper,
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Status;
+
+impl SlashCommand for Status {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut data: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+		Box::pin(async move {
+			data.ephemeral();
+
+			let mut stages = BTreeMap::new();
+
+			for info in helper.cluster().info().values() {
+				*stages
+					.entry(format!("{:?}", info.stage()).to_lowercase())
+					.or_insert(0_usize) += 1;
+			}
+
+			let stages = stages
+				.into_iter()
+				.map(|(stage, count)| format!("{} {}", count, stage))
+				.collect::<Vec<_>>()
+				.join(", ");
+
+			data.message(format!(
+				"**shards:** {}\n{}",
+				stages,
+				latency_report(&shard_latencies(&helper))
+			));
+
+			helper.respond(&mut data).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Status {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"status".to_owned(),
+			"Shows how the bot's shards are doing".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+	}
+
+	fn parse(_: CommandData) -> Result<Self> {
+		Ok(Self)
+	}
+}
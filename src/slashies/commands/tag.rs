@@ -1,6 +1,7 @@
 use std::{hint::unreachable_unchecked, pin::Pin};
 
 use futures_util::{Future, FutureExt};
+use twilight_gateway::Intents;
 use twilight_model::{
 	application::{
 		command::{CommandOptionChoice, CommandType},
@@ -323,6 +324,8 @@ impl SlashCommand for Tag {
 }
 
 impl DefineCommand for Tag {
+	const INTENTS: Intents = Intents::GUILDS.union(Intents::GUILD_MEMBERS);
+
 	fn define() -> CommandBuilder {
 		CommandBuilder::new(
 			"tag".to_owned(),
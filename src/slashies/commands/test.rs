@@ -0,0 +1,191 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_embed_builder::EmbedBuilder;
+use twilight_model::{
+	application::{command::CommandType, interaction::application_command::CommandData},
+	channel::embed::Embed,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::play::{
+	ansi_option, channel_option, code_option, edition_option, fresh_option, resolve_code, use_ansi,
+};
+use crate::{
+	helpers::{
+		parsing::{CodeBlock, CodeInput, CommandParse},
+		playground::{
+			add_embed_fields, truncate, BuildMode, CrateType, Edition, PlaygroundOutput,
+			PlaygroundRequest, RustChannel, TestReport, EMBED_FIELD_VALUE_LENGTH,
+		},
+		InteractionsHelper, STARLIGHT_COLORS,
+	},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+const FAILURE_OUTPUT_LENGTH: usize = EMBED_FIELD_VALUE_LENGTH - "```\n\n```".len();
+
+#[derive(Debug, Clone)]
+pub struct Test {
+	code: CodeInput,
+	channel: RustChannel,
+	edition: Edition,
+	ansi: Option<bool>,
+	fresh: bool,
+}
+
+impl Test {
+	fn report_embed(report: &TestReport) -> Result<Embed> {
+		let title = if report.is_success() {
+			"Tests passed"
+		} else {
+			"Tests failed"
+		};
+		let description = format!(
+			"{} passed, {} failed, {} ignored",
+			report.passed, report.failed, report.ignored
+		);
+
+		let failures = report
+			.failures
+			.iter()
+			.map(|failure| {
+				let output = if failure.output.is_empty() {
+					"no output".to_owned()
+				} else {
+					CodeBlock::new(truncate(&failure.output, FAILURE_OUTPUT_LENGTH), None)
+						.to_string()
+				};
+
+				(failure.name.clone(), output)
+			})
+			.collect();
+
+		let embed_builder = EmbedBuilder::new()
+			.color(STARLIGHT_COLORS[usize::from(!report.is_success())].to_decimal())
+			.title(title)
+			.description(&description);
+
+		add_embed_fields(embed_builder, title.len() + description.len(), failures)
+			.build()
+			.into_diagnostic()
+	}
+}
+
+impl SlashCommand for Test {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			let code = match resolve_code(&helper, &mut responder, &self.code).await? {
+				Some(code) => code,
+				None => return Ok(()),
+			};
+
+			helper.ack(&responder).await.into_diagnostic()?;
+
+			let request = PlaygroundRequest {
+				crate_type: CrateType::Library,
+				..PlaygroundRequest::new(&code, self.channel, self.edition, BuildMode::Debug, true)
+			};
+
+			let mut response = if self.fresh {
+				helper.playground().execute_uncached(&request).await?
+			} else {
+				helper.playground().execute(&request).await?
+			};
+
+			if let Some(report) = TestReport::parse(&response.stdout) {
+				responder.embed(Self::report_embed(&report)?);
+			} else {
+				response.format(false);
+
+				let ansi = use_ansi(&helper, &responder, self.ansi).await;
+
+				PlaygroundOutput::new(helper.playground(), &response, &request, ansi)
+					.await
+					.apply(&mut responder);
+			}
+
+			helper.update(&mut responder).await?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Test {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"test".to_owned(),
+			"Run the #[test] functions in some code".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(code_option("The code to test"))
+		.option(channel_option())
+		.option(edition_option())
+		.option(ansi_option())
+		.option(fresh_option())
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		let mut code: Option<String> = None;
+		let mut test = Self {
+			code: CodeInput::Missing,
+			channel: RustChannel::default(),
+			edition: Edition::default(),
+			ansi: None,
+			fresh: false,
+		};
+
+		for option in data.options {
+			match option.name.as_str() {
+				"code" => code = option.value.parse_option(),
+				"channel" => test.channel = option.value.parse_option().unwrap_or_default(),
+				"edition" => test.edition = option.value.parse_option().unwrap_or_default(),
+				"ansi" => test.ansi = option.value.parse_option(),
+				"fresh" => test.fresh = option.value.parse_option().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		test.code = CodeInput::new(code);
+
+		Ok(test)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Test;
+	use crate::helpers::playground::{TestFailure, TestReport};
+
+	#[test]
+	fn report_embed_fits_limits() {
+		let report = TestReport {
+			failed: 40,
+			failures: (0..40)
+				.map(|i| TestFailure {
+					name: format!("tests::{}_{}", "long_name".repeat(40), i),
+					output: "assertion failed\n".repeat(200),
+				})
+				.collect(),
+			..TestReport::default()
+		};
+
+		let embed = Test::report_embed(&report).unwrap();
+
+		assert!(!embed.fields.is_empty());
+		assert!(embed.fields.iter().all(|field| field.name.len() <= 256
+			&& field.value.len() <= 1024
+			&& field.value.ends_with("```")));
+		assert_eq!(
+			embed.footer.map(|footer| footer.text),
+			Some(format!("…and {} more", 40 - embed.fields.len()))
+		);
+	}
+}
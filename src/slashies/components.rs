@@ -0,0 +1,379 @@
+use std::str::FromStr;
+
+use twilight_model::{
+	application::{
+		callback::{CallbackData, InteractionResponse},
+		component::{
+			button::ButtonStyle, select_menu::SelectMenuOption, ActionRow, Button, Component,
+			SelectMenu,
+		},
+		interaction::MessageComponentInteraction,
+	},
+	channel::message::MessageFlags,
+};
+
+use super::SlashData;
+use crate::{
+	helpers::{
+		error_codes,
+		playground::{
+			truncate, Diagnostic, InvalidTypeError, PlaygroundOutput, PlaygroundSession,
+			RustChannel,
+		},
+		rate_limit::retry_message,
+		InteractionsHelper,
+	},
+	prelude::*,
+};
+
+fn ephemeral_message(content: String) -> InteractionResponse {
+	InteractionResponse::ChannelMessageWithSource(CallbackData {
+		content: Some(content),
+		flags: Some(MessageFlags::EPHEMERAL),
+		..SlashData::BASE
+	})
+}
+
+pub async fn respond_with_session(
+	helper: &InteractionsHelper,
+	mut responder: SlashData,
+	session: PlaygroundSession,
+) -> Result<()> {
+	let output = session.run(helper.playground()).await?;
+
+	responder.callback = PlaygroundAction::message(&session, &output, false);
+	responder.attachments = output.attachments.clone();
+
+	helper.update(&mut responder).await?;
+
+	let message = helper.raw_get(&responder).await?;
+
+	helper
+		.playground()
+		.sessions()
+		.insert(message.id, session, output);
+
+	Ok(())
+}
+
+// discord allows at most 25 options per select menu, with descriptions of up to 100 characters
+const MAX_MENU_OPTIONS: usize = 25;
+const MENU_DESCRIPTION_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExplainMenu;
+
+impl ExplainMenu {
+	pub const PREFIX: &'static str = "explain";
+
+	#[must_use]
+	pub fn component(codes: &[&str]) -> Option<Component> {
+		if codes.is_empty() {
+			return None;
+		}
+
+		let options = codes
+			.iter()
+			.take(MAX_MENU_OPTIONS)
+			.map(|code| SelectMenuOption {
+				default: false,
+				description: error_codes::explain(code).map(|(_, explanation)| {
+					truncate(error_codes::summary(explanation), MENU_DESCRIPTION_LENGTH).to_owned()
+				}),
+				emoji: None,
+				label: (*code).to_owned(),
+				value: (*code).to_owned(),
+			})
+			.collect();
+
+		Some(Component::ActionRow(ActionRow {
+			components: vec![Component::SelectMenu(SelectMenu {
+				custom_id: Self::PREFIX.to_owned(),
+				disabled: false,
+				max_values: Some(1),
+				min_values: Some(1),
+				options,
+				placeholder: Some("Explain an error code".to_owned()),
+			})],
+		}))
+	}
+
+	pub async fn run(
+		helper: &InteractionsHelper,
+		component: MessageComponentInteraction,
+	) -> Result<()> {
+		let explanation = component
+			.data
+			.values
+			.first()
+			.and_then(|code| error_codes::explain(code));
+
+		let response = match explanation {
+			Some((code, explanation)) => {
+				InteractionResponse::ChannelMessageWithSource(CallbackData {
+					embeds: Some(vec![error_codes::embed(code, explanation)?]),
+					flags: Some(MessageFlags::EPHEMERAL),
+					..SlashData::BASE
+				})
+			}
+			None => ephemeral_message("unknown error code".to_owned()),
+		};
+
+		helper
+			.respond_component(&component, &response)
+			.await
+			.into_diagnostic()
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaygroundAction {
+	Share,
+	Rerun(RustChannel),
+	Delete,
+	ShowRaw,
+	ShowDiagnostics,
+}
+
+impl PlaygroundAction {
+	pub const PREFIX: &'static str = "playground";
+
+	#[must_use]
+	pub fn message(
+		session: &PlaygroundSession,
+		output: &PlaygroundOutput,
+		raw: bool,
+	) -> CallbackData {
+		let mut callback = CallbackData {
+			content: Some(output.content.clone()),
+			embeds: Some(Vec::new()),
+			..SlashData::BASE
+		};
+
+		let mut components = ExplainMenu::component(&output.error_codes)
+			.into_iter()
+			.collect::<Vec<_>>();
+
+		if !output.diagnostics.is_empty() {
+			let embed = if raw {
+				None
+			} else {
+				Diagnostic::embed(&output.diagnostics)
+					.map_err(|e| {
+						event!(
+							Level::WARN,
+							error = &*e.root_cause(),
+							"failed to build the diagnostics embed"
+						);
+					})
+					.ok()
+			};
+
+			let toggle = match embed {
+				Some(embed) => {
+					callback.content = Some(output.program_output());
+					callback.embeds = Some(vec![embed]);
+
+					Some(Self::ShowRaw.button(
+						"Raw output".to_owned(),
+						ButtonStyle::Secondary,
+						false,
+					))
+				}
+				None if raw => Some(Self::ShowDiagnostics.button(
+					"Show diagnostics".to_owned(),
+					ButtonStyle::Secondary,
+					false,
+				)),
+				None => None,
+			};
+
+			components.extend(toggle.map(|toggle| {
+				Component::ActionRow(ActionRow {
+					components: vec![toggle],
+				})
+			}));
+		}
+
+		components.extend(Self::components(session, output.cached));
+
+		callback.components = Some(components);
+
+		callback
+	}
+
+	// a cached result can be re-run on its own channel, which is the only way to skip the cache
+	// from the message command
+	#[must_use]
+	pub fn components(session: &PlaygroundSession, cached: bool) -> Vec<Component> {
+		let mut buttons = vec![Self::Share.button("Share".to_owned(), ButtonStyle::Primary, false)];
+
+		for channel in [RustChannel::Stable, RustChannel::Beta, RustChannel::Nightly] {
+			buttons.push(Self::Rerun(channel).button(
+				format!("Re-run on {}", channel),
+				ButtonStyle::Secondary,
+				channel == session.channel && !cached,
+			));
+		}
+
+		if !session.ephemeral {
+			buttons.push(Self::Delete.button("Delete".to_owned(), ButtonStyle::Danger, false));
+		}
+
+		vec![Component::ActionRow(ActionRow {
+			components: buttons,
+		})]
+	}
+
+	pub async fn run(
+		self,
+		helper: &InteractionsHelper,
+		component: MessageComponentInteraction,
+	) -> Result<()> {
+		let sessions = helper.playground().sessions();
+
+		let (session, output) = if let Some(stored) = sessions.get(component.message.id) {
+			stored
+		} else {
+			return helper
+				.respond_component(
+					&component,
+					&ephemeral_message(
+						"this result has expired, run the command again to use its buttons"
+							.to_owned(),
+					),
+				)
+				.await
+				.into_diagnostic();
+		};
+
+		let user_id = component
+			.member
+			.as_ref()
+			.and_then(|member| member.user.as_ref())
+			.or_else(|| component.user.as_ref())
+			.map(|user| user.id);
+
+		if let (Self::Share | Self::Rerun(_), Some(user_id)) = (self, user_id) {
+			if let Err(retry_after) = helper.check_rate_limit(user_id, component.guild_id).await {
+				return helper
+					.respond_component(&component, &ephemeral_message(retry_message(retry_after)))
+					.await
+					.into_diagnostic();
+			}
+		}
+
+		match self {
+			Self::Share => {
+				let url = helper.playground().share(&session.request()).await?;
+
+				helper
+					.respond_component(&component, &ephemeral_message(url))
+					.await
+					.into_diagnostic()?;
+			}
+			Self::Rerun(channel) => {
+				helper
+					.respond_component(&component, &InteractionResponse::DeferredUpdateMessage)
+					.await
+					.into_diagnostic()?;
+
+				let session = PlaygroundSession {
+					channel,
+					fresh: true,
+					..session
+				};
+
+				let output = session.run(helper.playground()).await?;
+
+				let callback = Self::message(&session, &output, false);
+
+				helper
+					.update_original(&component.token, &callback, &output.attachments)
+					.await?;
+
+				sessions.insert(component.message.id, session, output);
+			}
+			Self::Delete => {
+				if user_id != Some(session.author) {
+					return helper
+						.respond_component(
+							&component,
+							&ephemeral_message(
+								"only the person who ran this code can delete it".to_owned(),
+							),
+						)
+						.await
+						.into_diagnostic();
+				}
+
+				helper
+					.respond_component(&component, &InteractionResponse::DeferredUpdateMessage)
+					.await
+					.into_diagnostic()?;
+
+				helper
+					.delete_original(&component.token)
+					.await
+					.into_diagnostic()?;
+
+				sessions.remove(component.message.id);
+			}
+			Self::ShowRaw | Self::ShowDiagnostics => {
+				let callback = Self::message(&session, &output, self == Self::ShowRaw);
+
+				helper
+					.respond_component(&component, &InteractionResponse::UpdateMessage(callback))
+					.await
+					.into_diagnostic()?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn button(self, label: String, style: ButtonStyle, disabled: bool) -> Component {
+		Component::Button(Button {
+			custom_id: Some(self.to_string()),
+			disabled,
+			emoji: None,
+			label: Some(label),
+			style,
+			url: None,
+		})
+	}
+}
+
+impl Display for PlaygroundAction {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str(Self::PREFIX)?;
+
+		match self {
+			Self::Share => f.write_str(":share"),
+			Self::Rerun(channel) => write!(f, ":rerun:{}", channel),
+			Self::Delete => f.write_str(":delete"),
+			Self::ShowRaw => f.write_str(":raw"),
+			Self::ShowDiagnostics => f.write_str(":diagnostics"),
+		}
+	}
+}
+
+impl FromStr for PlaygroundAction {
+	type Err = InvalidTypeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let action = s
+			.strip_prefix(Self::PREFIX)
+			.and_then(|action| action.strip_prefix(':'))
+			.ok_or_else(InvalidTypeError::default)?;
+
+		match action.split_once(':') {
+			Some(("rerun", channel)) => Ok(Self::Rerun(channel.parse()?)),
+			None if action == "share" => Ok(Self::Share),
+			None if action == "delete" => Ok(Self::Delete),
+			None if action == "raw" => Ok(Self::ShowRaw),
+			None if action == "diagnostics" => Ok(Self::ShowDiagnostics),
+			_ => Err(InvalidTypeError::default()),
+		}
+	}
+}
@@ -1,6 +1,7 @@
 use std::pin::Pin;
 
 use futures_util::Future;
+use twilight_gateway::Intents;
 use twilight_model::application::interaction::application_command::CommandData;
 use twilight_util::builder::command::CommandBuilder;
 
@@ -25,6 +26,8 @@ pub trait SlashCommand: Send + Sync {
 }
 
 pub trait DefineCommand: SlashCommand + Sized {
+	const INTENTS: Intents = Intents::empty();
+
 	fn define() -> CommandBuilder;
 
 	fn parse(data: CommandData) -> Result<Self>;
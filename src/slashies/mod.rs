@@ -1,12 +1,14 @@
 pub mod commands;
+pub mod components;
 mod r#impl;
 
-use std::{fmt::Write, ops::Deref};
+use std::{fmt::Write, mem, ops::Deref};
 
 use twilight_model::{
 	application::{
 		callback::{Autocomplete, CallbackData},
 		command::CommandOptionChoice,
+		component::Component,
 		interaction::ApplicationCommand,
 	},
 	channel::{
@@ -18,7 +20,7 @@ use twilight_model::{
 };
 
 pub use self::r#impl::{DefineCommand, SlashCommand};
-use crate::prelude::*;
+use crate::{helpers::parsing::CodeBlock, prelude::*};
 
 #[derive(Debug, Clone)]
 #[must_use = "SlashData has no side effects"]
@@ -26,6 +28,7 @@ pub struct SlashData {
 	pub callback: CallbackData,
 	pub command: ApplicationCommand,
 	pub autocomplete: Autocomplete,
+	pub attachments: Vec<(String, Vec<u8>)>,
 }
 
 impl SlashData {
@@ -37,12 +40,14 @@ impl SlashData {
 		components: None,
 		tts: None,
 	};
+	pub const MAX_CONTENT_LENGTH: usize = 2000;
 
 	pub const fn new(command: ApplicationCommand) -> Self {
 		Self {
 			callback: Self::BASE,
 			command,
 			autocomplete: Autocomplete { choices: vec![] },
+			attachments: Vec::new(),
 		}
 	}
 
@@ -102,6 +107,20 @@ impl SlashData {
 		self
 	}
 
+	pub fn code_block(&mut self, code: String, language: &str, filename: &str) -> &mut Self {
+		let code_block = CodeBlock::new(code.trim(), Some(language)).to_string();
+
+		if code_block.len() > Self::MAX_CONTENT_LENGTH {
+			self.message(format!(
+				"the code is too long, so it was attached as `{}`",
+				filename
+			))
+			.attachment(filename.to_owned(), code.into_bytes())
+		} else {
+			self.message(code_block)
+		}
+	}
+
 	pub fn autocomplete(&mut self, choices: Vec<CommandOptionChoice>) -> &mut Self {
 		self.autocomplete = Autocomplete { choices };
 
@@ -124,6 +143,22 @@ impl SlashData {
 		self.embeds(vec![embed])
 	}
 
+	pub fn components(&mut self, components: Vec<Component>) -> &mut Self {
+		if let Some(current_components) = &mut self.callback.components {
+			current_components.extend(components);
+		} else {
+			self.callback.components = Some(components);
+		}
+
+		self
+	}
+
+	pub fn attachment(&mut self, filename: String, content: Vec<u8>) -> &mut Self {
+		self.attachments.push((filename, content));
+
+		self
+	}
+
 	pub fn flags(&mut self, flags: MessageFlags) -> &mut Self {
 		self.callback.flags = self
 			.callback
@@ -149,6 +184,7 @@ impl SlashData {
 			},
 			command: self.command.clone(),
 			autocomplete: self.autocomplete.clone(),
+			attachments: mem::take(&mut self.attachments),
 		}
 	}
 }
@@ -1,7 +1,7 @@
 use std::{
 	env::VarError,
 	path::{Path, PathBuf},
-	sync::Arc,
+	sync::{Arc, RwLock},
 };
 
 use starchart::Starchart;
@@ -9,20 +9,21 @@ use starlight_macros::cloned;
 use thiserror::Error;
 use twilight_cache_inmemory::InMemoryCacheBuilder;
 use twilight_gateway::{
-	shard::{Events, ShardBuilder},
+	cluster::{ClusterBuilder, Events, ShardScheme},
 	Intents,
 };
 use twilight_http::client::ClientBuilder;
 
-use super::{Config, Context, State};
-use crate::prelude::*;
+use super::{events, Config, Context, State};
+use crate::{
+	helpers::{playground::PlaygroundClient, InteractionsHelper},
+	prelude::*,
+};
 
 #[derive(Debug, Error)]
 pub enum ContextBuildError {
-	#[error("intents not set")]
-	Intents,
-	#[error("shard builder not set")]
-	Shard,
+	#[error("cluster builder not set")]
+	Cluster,
 	#[error("database path not set")]
 	Database,
 }
@@ -30,25 +31,27 @@ pub enum ContextBuildError {
 #[derive(Debug, Default)]
 #[must_use = "a context builder has no side effects"]
 pub struct ContextBuilder {
-	shard: Option<ShardBuilder>,
+	cluster: Option<ClusterBuilder>,
 	cache: Option<InMemoryCacheBuilder>,
 	http: Option<ClientBuilder>,
 	intents: Option<Intents>,
 	cdn: Option<reqwest::ClientBuilder>,
 	config: Option<Config>,
 	database_path: Option<PathBuf>,
+	token: Option<String>,
 }
 
 impl ContextBuilder {
 	pub const fn new() -> Self {
 		Self {
-			shard: None,
+			cluster: None,
 			cache: None,
 			http: None,
 			intents: None,
 			config: None,
 			cdn: None,
 			database_path: None,
+			token: None,
 		}
 	}
 
@@ -64,24 +67,58 @@ impl ContextBuilder {
 		self
 	}
 
-	pub fn shard_builder<F>(mut self, shard_builder: F) -> Result<Self>
+	// takes priority over the token from the environment
+	pub fn token(mut self, token: String) -> Self {
+		self.token = Some(token);
+
+		self
+	}
+
+	fn get_token(&self) -> Result<String, VarError> {
+		self.token.clone().map_or_else(Config::token, Ok)
+	}
+
+	pub fn cluster_builder<F>(mut self, cluster_builder: F) -> Result<Self>
 	where
-		F: FnOnce(ShardBuilder) -> ShardBuilder,
+		F: FnOnce(ClusterBuilder) -> ClusterBuilder,
 	{
-		let intents = self
-			.intents
-			.ok_or(ContextBuildError::Intents)
-			.into_diagnostic()
-			.context("need intents to build shard")?;
-		let token = Config::token().into_diagnostic()?;
+		let intents = self.gateway_intents();
+		let token = self.get_token().into_diagnostic()?;
 
-		let shard = shard_builder((token, intents).into());
+		let cluster = cluster_builder(ClusterBuilder::new(token, intents));
 
-		self.shard = Some(shard);
+		self.cluster = Some(cluster);
 
 		Ok(self)
 	}
 
+	// intents set on the builder take priority over the configured ones, but either can only add to
+	// what the commands and event handlers need, a missing one is enabled anyway since leaving it
+	// out would quietly break whatever needs it
+	fn gateway_intents(&self) -> Intents {
+		let required = events::INTENTS | InteractionsHelper::required_intents();
+		let configured = self
+			.intents
+			.or_else(|| self.config.as_ref().and_then(|config| config.intents));
+
+		let configured = match configured {
+			Some(configured) => configured,
+			None => return required,
+		};
+
+		let missing = required - configured;
+
+		if !missing.is_empty() {
+			event!(
+				Level::WARN,
+				?missing,
+				"configured intents leave out some that handlers need, they're enabled anyway"
+			);
+		}
+
+		configured | required
+	}
+
 	pub const fn cache(mut self, cache_builder: InMemoryCacheBuilder) -> Self {
 		self.cache = Some(cache_builder);
 
@@ -109,7 +146,7 @@ impl ContextBuilder {
 	where
 		F: FnOnce(ClientBuilder) -> ClientBuilder,
 	{
-		let token = Config::token()?;
+		let token = self.get_token()?;
 		let http_builder = self
 			.http
 			.map_or_else(move || ClientBuilder::new().token(token), |builder| builder);
@@ -122,14 +159,14 @@ impl ContextBuilder {
 	}
 
 	pub async fn build(self) -> Result<(Context, Events)> {
+		let token = self.get_token().into_diagnostic()?;
 		let config = self.config.unwrap_or_default();
-		let token = Config::token().into_diagnostic()?;
 		let http_builder = self
 			.http
 			.unwrap_or_else(cloned!(token => move || ClientBuilder::new().token(token)));
-		let shard_builder: ShardBuilder = self
-			.shard
-			.ok_or(ContextBuildError::Shard)
+		let mut cluster_builder: ClusterBuilder = self
+			.cluster
+			.ok_or(ContextBuildError::Cluster)
 			.into_diagnostic()
 			.context("need cluster to build state")?;
 		let cdn_builder = self.cdn.unwrap_or_default();
@@ -143,23 +180,40 @@ impl ContextBuilder {
 
 		let http = Arc::new(http_builder.token(token).build());
 		let cache = Arc::new(cache_builder.build());
-		let (shard, events) = shard_builder.http_client(Arc::clone(&http)).build();
+
+		if let Some(shards) = config.shards {
+			cluster_builder = cluster_builder.shard_scheme(ShardScheme::Range {
+				from: shards.from,
+				to: shards.to,
+				total: shards.total,
+			});
+		}
+
+		let (cluster, events) = cluster_builder
+			.http_client(Arc::clone(&http))
+			.build()
+			.await
+			.into_diagnostic()?;
 		let cdn = cdn_builder.build().into_diagnostic()?;
+		let playground = PlaygroundClient::from_config(&config)?;
 		let standby = Arc::default();
 		let backend = TomlBackend::new(db_path).into_diagnostic()?;
 
 		let database = Starchart::new(backend).await.into_diagnostic()?;
 
-		let components = Box::leak(Box::new(State {
+		let state = State {
 			cache,
-			shard: Arc::new(shard),
+			cluster: Arc::new(cluster),
 			standby,
 			http,
 			cdn,
-			config,
+			playground,
+			rate_limiter: Arc::default(),
+			config: Arc::new(RwLock::new(Arc::new(config))),
 			database,
-		}));
+			handlers: Arc::default(),
+		};
 
-		Ok((Context(components), events))
+		Ok((Context(Arc::new(state)), events))
 	}
 }
@@ -0,0 +1,291 @@
+use std::{
+	ops::BitOr,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use reqwest::Url;
+use serde::Deserialize;
+use thiserror::Error;
+use toml::Spanned;
+use tracing_subscriber::EnvFilter;
+use twilight_cache_inmemory::ResourceType;
+use twilight_gateway::Intents;
+use twilight_model::id::Id;
+
+use super::{command_name, flag_from_name, Config, LogFormat, INTENT_NAMES, RESOURCE_TYPE_NAMES};
+use crate::helpers::rate_limit::RateLimit;
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("{message}")]
+#[diagnostic(code(starlight::config::invalid_file))]
+pub struct ConfigFileError {
+	message: String,
+	#[source_code]
+	source_code: NamedSource,
+	#[label("here")]
+	span: SourceSpan,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+	database_path: Option<PathBuf>,
+	cache_resource_types: Option<Vec<Spanned<String>>>,
+	intents: Option<Vec<Spanned<String>>>,
+	owners: Option<Vec<Spanned<u64>>>,
+	#[serde(default)]
+	log: LogSection,
+	#[serde(default)]
+	playground: PlaygroundSection,
+	#[serde(default)]
+	crates_io: CratesIoSection,
+	#[serde(default)]
+	rate_limits: RateLimitSection,
+	#[serde(default)]
+	features: FeatureSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LogSection {
+	format: Option<Spanned<String>>,
+	filter: Option<Spanned<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlaygroundSection {
+	url: Option<Spanned<String>>,
+	timeout: Option<u64>,
+	user_agent: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CratesIoSection {
+	url: Option<Spanned<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RateLimitSection {
+	user: Option<Spanned<String>>,
+	guild: Option<Spanned<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeatureSection {
+	disabled_commands: Option<Vec<Spanned<String>>>,
+}
+
+struct Source<'a> {
+	path: &'a Path,
+	source: &'a str,
+}
+
+impl Source<'_> {
+	fn error(&self, start: usize, end: usize, message: String) -> ConfigFileError {
+		ConfigFileError {
+			message,
+			source_code: NamedSource::new(self.path.display().to_string(), self.source.to_owned()),
+			span: (start, end - start).into(),
+		}
+	}
+
+	fn invalid<T>(&self, value: &Spanned<T>, message: String) -> ConfigFileError {
+		self.error(value.start(), value.end(), message)
+	}
+
+	// toml only gives a line and column, so the span covers the rest of that line
+	fn parse_error(&self, error: &toml::de::Error) -> ConfigFileError {
+		let (start, end) = error.line_col().map_or((0, 0), |(line, column)| {
+			let start = self
+				.source
+				.split_inclusive('\n')
+				.take(line)
+				.map(str::len)
+				.sum::<usize>();
+			let line = self.source[start..].lines().next().unwrap_or_default();
+
+			(start + column.min(line.len()), start + line.len())
+		});
+
+		self.error(start, end, error.to_string())
+	}
+
+	fn url(&self, url: Spanned<String>) -> Result<String, ConfigFileError> {
+		if let Err(e) = Url::parse(url.get_ref()) {
+			return Err(self.invalid(&url, format!("invalid URL: {}", e)));
+		}
+
+		Ok(url.into_inner().trim_end_matches('/').to_owned())
+	}
+
+	fn rate_limit(&self, rate_limit: &Spanned<String>) -> Result<RateLimit, ConfigFileError> {
+		rate_limit
+			.get_ref()
+			.parse::<RateLimit>()
+			.map_err(|e| self.invalid(rate_limit, e.to_string()))
+	}
+
+	fn flags<T: Copy + BitOr<Output = T>>(
+		&self,
+		values: &[Spanned<String>],
+		names: &[(&str, T)],
+		empty: T,
+		kind: &str,
+	) -> Result<T, ConfigFileError> {
+		values.iter().try_fold(empty, |acc, name| {
+			flag_from_name(names, name.get_ref())
+				.map(|flag| acc | flag)
+				.ok_or_else(|| {
+					self.invalid(name, format!("`{}` isn't a known {}", name.get_ref(), kind))
+				})
+		})
+	}
+}
+
+pub(super) fn apply(path: &Path, source: &str, config: &mut Config) -> Result<(), ConfigFileError> {
+	let source = Source { path, source };
+
+	let file: ConfigFile = toml::from_str(source.source).map_err(|e| source.parse_error(&e))?;
+
+	if let Some(database_path) = file.database_path {
+		config.database_path = database_path;
+	}
+
+	if let Some(names) = &file.cache_resource_types {
+		config.cache_resource_types = source.flags(
+			names,
+			&RESOURCE_TYPE_NAMES,
+			ResourceType::empty(),
+			"cache resource type",
+		)?;
+	}
+
+	if let Some(names) = &file.intents {
+		config.intents =
+			Some(source.flags(names, &INTENT_NAMES, Intents::empty(), "gateway intent")?);
+	}
+
+	if let Some(owners) = &file.owners {
+		config.owners = owners
+			.iter()
+			.map(|id| {
+				Id::new_checked(*id.get_ref())
+					.ok_or_else(|| source.invalid(id, "owner IDs can't be 0".to_owned()))
+			})
+			.collect::<Result<_, _>>()?;
+	}
+
+	if let Some(format) = &file.log.format {
+		config.log_format = format
+			.get_ref()
+			.parse::<LogFormat>()
+			.map_err(|e| source.invalid(format, e.to_string()))?;
+	}
+
+	if let Some(filter) = file.log.filter {
+		if let Err(e) = EnvFilter::try_new(filter.get_ref()) {
+			return Err(source.invalid(&filter, format!("invalid log filter: {}", e)));
+		}
+
+		config.log_filter = Some(filter.into_inner());
+	}
+
+	if let Some(url) = file.playground.url {
+		config.playground_url = source.url(url)?;
+	}
+
+	if let Some(timeout) = file.playground.timeout {
+		config.playground_timeout = Duration::from_secs(timeout);
+	}
+
+	if let Some(user_agent) = file.playground.user_agent {
+		config.playground_user_agent = user_agent;
+	}
+
+	if let Some(url) = file.crates_io.url {
+		config.crates_io_url = source.url(url)?;
+	}
+
+	if let Some(rate_limit) = &file.rate_limits.user {
+		config.rate_limits.user = source.rate_limit(rate_limit)?;
+	}
+
+	if let Some(rate_limit) = &file.rate_limits.guild {
+		config.rate_limits.guild = source.rate_limit(rate_limit)?;
+	}
+
+	if let Some(names) = &file.features.disabled_commands {
+		config.disabled_commands = names
+			.iter()
+			.map(|name| {
+				command_name(name.get_ref())
+					.map(ToOwned::to_owned)
+					.ok_or_else(|| {
+						source.invalid(name, format!("`{}` isn't a known command", name.get_ref()))
+					})
+			})
+			.collect::<Result<_, _>>()?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+
+	use twilight_gateway::Intents;
+
+	use super::apply;
+	use crate::state::{Config, LogFormat};
+
+	#[test]
+	fn apply_file() {
+		let mut config = Config::default();
+
+		apply(
+			Path::new("starlight.toml"),
+			r#"
+				intents = ["GUILDS", "guild_messages"]
+				owners = [1234]
+
+				[log]
+				format = "compact"
+
+				[crates_io]
+				url = "https://example.com/api/"
+
+				[features]
+				disabled_commands = ["eval", "Run code"]
+			"#,
+			&mut config,
+		)
+		.unwrap();
+
+		assert_eq!(
+			config.intents,
+			Some(Intents::GUILDS | Intents::GUILD_MESSAGES)
+		);
+		assert_eq!(config.owners.len(), 1);
+		assert_eq!(config.log_format, LogFormat::Compact);
+		assert_eq!(config.crates_io_url, "https://example.com/api");
+		assert_eq!(config.playground_url, Config::default().playground_url);
+		assert_eq!(config.disabled_commands, ["eval", "Run code"]);
+	}
+
+	#[test]
+	fn error_points_at_value() {
+		let source = "owners = [1]\nintents = [\"GUILDS\", \"GUILD_SPEAKING\"]\n";
+
+		let error = apply(Path::new("starlight.toml"), source, &mut Config::default()).unwrap_err();
+
+		let span = error.span.offset()..error.span.offset() + error.span.len();
+		assert_eq!(source[span].trim_matches('"'), "GUILD_SPEAKING");
+	}
+}
@@ -0,0 +1,734 @@
+use std::{
+	env::{self, VarError},
+	fmt::Display,
+	fs,
+	ops::BitOr,
+	path::{Path, PathBuf},
+	str::FromStr,
+	time::Duration,
+};
+
+use clap::{
+	crate_authors, crate_description, crate_name, crate_version, App, Arg, ArgMatches,
+	Error as ClapError, FromArgMatches, IntoApp, Parser,
+};
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use reqwest::Url;
+use thiserror::Error;
+use tracing::{event, instrument, Level};
+use tracing_subscriber::EnvFilter;
+use twilight_cache_inmemory::ResourceType;
+use twilight_gateway::Intents;
+use twilight_model::id::{
+	marker::{ApplicationMarker, GuildMarker, UserMarker},
+	Id,
+};
+
+pub use self::file::ConfigFileError;
+use crate::helpers::{
+	rate_limit::{RateLimit, RateLimits},
+	InteractionsHelper,
+};
+
+mod file;
+
+const CONFIG: &str = "config";
+const REMOVE_SLASH_COMMANDS: &str = "remove-slash-commands";
+const GUILD_ID: &str = "guild-id";
+const DATABASE_PATH: &str = "database-path";
+const CACHE_RESOURCE_TYPES: &str = "cache-resource-types";
+const OWNERS: &str = "owners";
+const LOG_FORMAT: &str = "log-format";
+const LOG_FILTER: &str = "log-filter";
+const PLAYGROUND_URL: &str = "playground-url";
+const PLAYGROUND_TIMEOUT: &str = "playground-timeout";
+const PLAYGROUND_USER_AGENT: &str = "playground-user-agent";
+const CRATES_IO_URL: &str = "crates-io-url";
+const USER_RATE_LIMIT: &str = "user-rate-limit";
+const GUILD_RATE_LIMIT: &str = "guild-rate-limit";
+const SHARD_COUNT: &str = "shard-count";
+const SHARD_RANGE: &str = "shard-range";
+const INTENTS: &str = "intents";
+const DISABLED_COMMANDS: &str = "disabled-commands";
+
+const DEFAULT_CONFIG_PATH: &str = "starlight.toml";
+const DEFAULT_DATABASE_PATH: &str = "./target/db";
+const DEFAULT_PLAYGROUND_URL: &str = "https://play.rust-lang.org";
+const DEFAULT_PLAYGROUND_TIMEOUT: u64 = 30;
+const DEFAULT_USER_AGENT: &str = "pyrotechniac/starlight";
+const DEFAULT_CRATES_IO_URL: &str = "https://crates.io/api/v1";
+
+// static mut TOKEN: Option<&str> = None;
+const TOKEN: Option<&'static str> = option_env!("DISCORD_TOKEN");
+
+const INTENT_NAMES: [(&str, Intents); 15] = [
+	("GUILDS", Intents::GUILDS),
+	("GUILD_MEMBERS", Intents::GUILD_MEMBERS),
+	("GUILD_BANS", Intents::GUILD_BANS),
+	(
+		"GUILD_EMOJIS_AND_STICKERS",
+		Intents::GUILD_EMOJIS_AND_STICKERS,
+	),
+	("GUILD_INTEGRATIONS", Intents::GUILD_INTEGRATIONS),
+	("GUILD_WEBHOOKS", Intents::GUILD_WEBHOOKS),
+	("GUILD_INVITES", Intents::GUILD_INVITES),
+	("GUILD_VOICE_STATES", Intents::GUILD_VOICE_STATES),
+	("GUILD_PRESENCES", Intents::GUILD_PRESENCES),
+	("GUILD_MESSAGES", Intents::GUILD_MESSAGES),
+	("GUILD_MESSAGE_REACTIONS", Intents::GUILD_MESSAGE_REACTIONS),
+	("GUILD_MESSAGE_TYPING", Intents::GUILD_MESSAGE_TYPING),
+	("DIRECT_MESSAGES", Intents::DIRECT_MESSAGES),
+	(
+		"DIRECT_MESSAGE_REACTIONS",
+		Intents::DIRECT_MESSAGE_REACTIONS,
+	),
+	("DIRECT_MESSAGE_TYPING", Intents::DIRECT_MESSAGE_TYPING),
+];
+
+const RESOURCE_TYPE_NAMES: [(&str, ResourceType); 14] = [
+	("CHANNEL", ResourceType::CHANNEL),
+	("EMOJI", ResourceType::EMOJI),
+	("GUILD", ResourceType::GUILD),
+	("INTEGRATION", ResourceType::INTEGRATION),
+	("MEMBER", ResourceType::MEMBER),
+	("MESSAGE", ResourceType::MESSAGE),
+	("PRESENCE", ResourceType::PRESENCE),
+	("REACTION", ResourceType::REACTION),
+	("ROLE", ResourceType::ROLE),
+	("STAGE_INSTANCE", ResourceType::STAGE_INSTANCE),
+	("STICKER", ResourceType::STICKER),
+	("USER", ResourceType::USER),
+	("USER_CURRENT", ResourceType::USER_CURRENT),
+	("VOICE_STATE", ResourceType::VOICE_STATE),
+];
+
+static mut APPLICATION_ID: Option<Id<ApplicationMarker>> = None;
+
+fn flag_from_name<T: Copy>(names: &[(&str, T)], name: &str) -> Option<T> {
+	let normalized = name.trim().replace('-', "_");
+
+	names
+		.iter()
+		.find(|(flag_name, _)| flag_name.eq_ignore_ascii_case(&normalized))
+		.map(|(_, flag)| *flag)
+}
+
+fn flags_from_names<'a, T: Copy + BitOr<Output = T>>(
+	values: impl IntoIterator<Item = &'a str>,
+	names: &[(&str, T)],
+	empty: T,
+	kind: &str,
+) -> Result<T, ClapError> {
+	values
+		.into_iter()
+		.filter(|name| !name.trim().is_empty())
+		.try_fold(empty, |acc, name| {
+			let flag = flag_from_name(names, name).ok_or_else(|| {
+				ClapError::raw(
+					clap::ErrorKind::ValueValidation,
+					format!("`{}` isn't a known {}\n", name, kind),
+				)
+			})?;
+
+			Ok(acc | flag)
+		})
+}
+
+fn command_name(name: &str) -> Option<&'static str> {
+	InteractionsHelper::COMMAND_NAMES
+		.iter()
+		.find(|command| **command == name.trim())
+		.copied()
+}
+
+// `None` when the argument wasn't given, so whatever was set before it is kept
+fn value_of<T>(matches: &ArgMatches, name: &str) -> Result<Option<T>, ClapError>
+where
+	T: FromStr,
+	T::Err: Display,
+{
+	match matches.value_of_t::<T>(name) {
+		Ok(value) => Ok(Some(value)),
+		Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
+#[derive(Debug, Error, Clone, Copy)]
+#[error("the log format must be one of `pretty`, `compact` or `full`")]
+pub struct LogFormatParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+	Pretty,
+	Compact,
+	Full,
+}
+
+impl Default for LogFormat {
+	fn default() -> Self {
+		Self::Pretty
+	}
+}
+
+impl FromStr for LogFormat {
+	type Err = LogFormatParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"pretty" => Ok(Self::Pretty),
+			"compact" => Ok(Self::Compact),
+			"full" => Ok(Self::Full),
+			_ => Err(LogFormatParseError),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardRange {
+	pub from: u64,
+	pub to: u64,
+	pub total: u64,
+}
+
+impl ShardRange {
+	fn parse(range: &str, total: u64) -> Result<Self, ClapError> {
+		let invalid = || {
+			ClapError::raw(
+				clap::ErrorKind::ValueValidation,
+				format!(
+					"`{}` isn't a valid range within the {} shards, use `from-to`\n",
+					range, total
+				),
+			)
+		};
+		let parse = |value: &str| value.trim().parse::<u64>().map_err(|_| invalid());
+
+		let (from, to) = match range.split_once('-') {
+			Some((from, to)) => (parse(from)?, parse(to)?),
+			None => (parse(range)?, parse(range)?),
+		};
+
+		if from > to || to >= total {
+			return Err(invalid());
+		}
+
+		Ok(Self { from, to, total })
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+	pub guild_id: Option<Id<GuildMarker>>,
+	pub remove_slash_commands: bool,
+	pub database_path: PathBuf,
+	pub cache_resource_types: ResourceType,
+	pub owners: Vec<Id<UserMarker>>,
+	pub log_format: LogFormat,
+	pub log_filter: Option<String>,
+	pub playground_url: String,
+	pub playground_timeout: Duration,
+	pub playground_user_agent: String,
+	pub crates_io_url: String,
+	pub rate_limits: RateLimits,
+	pub shards: Option<ShardRange>,
+	pub intents: Option<Intents>,
+	pub disabled_commands: Vec<String>,
+	pub config_path: Option<PathBuf>,
+}
+
+impl Config {
+	// the config file is read first, then anything given on the command line or through the
+	// environment replaces what it set
+	pub fn load() -> Result<Self> {
+		let matches = Self::into_app().get_matches();
+
+		let mut config = Self::from_default_file(&matches)?;
+
+		if let Err(e) = config.update_from_arg_matches(&matches) {
+			e.exit();
+		}
+
+		Ok(config)
+	}
+
+	// arguments and the environment can't change while running, so anything new comes from the
+	// config file, and settings only read on startup keep their current values
+	pub fn reload(&self) -> Result<Self> {
+		if let Some(path) = &self.config_path {
+			if !path.exists() {
+				return Err(miette!(
+					"the config file `{}` was loaded on startup but doesn't exist anymore",
+					path.display()
+				));
+			}
+		}
+
+		let matches = Self::into_app().try_get_matches().into_diagnostic()?;
+
+		let mut config = Self::from_default_file(&matches)?;
+
+		if config.log_filter.is_some() && matches.is_present(LOG_FILTER) {
+			event!(
+				Level::WARN,
+				"`RUST_LOG` or `--log-filter` is set, so the log filter in the config file is ignored"
+			);
+		}
+
+		config.update_from_arg_matches(&matches).into_diagnostic()?;
+
+		Ok(config.with_startup_settings(self))
+	}
+
+	fn from_default_file(matches: &ArgMatches) -> Result<Self> {
+		match matches.value_of(CONFIG) {
+			Some(path) => Self::from_file(path),
+			None if Path::new(DEFAULT_CONFIG_PATH).exists() => Self::from_file(DEFAULT_CONFIG_PATH),
+			None => Ok(Self::default()),
+		}
+	}
+
+	fn with_startup_settings(self, current: &Self) -> Self {
+		let changed = [
+			("guild ID", self.guild_id != current.guild_id),
+			("database path", self.database_path != current.database_path),
+			(
+				"cache resource types",
+				self.cache_resource_types != current.cache_resource_types,
+			),
+			("log format", self.log_format != current.log_format),
+			("shards", self.shards != current.shards),
+			("intents", self.intents != current.intents),
+		];
+
+		for (setting, _) in changed.into_iter().filter(|(_, changed)| *changed) {
+			event!(
+				Level::WARN,
+				setting,
+				"this setting can't be changed while running, restart to apply it"
+			);
+		}
+
+		Self {
+			owners: self.owners,
+			log_filter: self.log_filter,
+			playground_url: self.playground_url,
+			playground_timeout: self.playground_timeout,
+			playground_user_agent: self.playground_user_agent,
+			crates_io_url: self.crates_io_url,
+			rate_limits: self.rate_limits,
+			disabled_commands: self.disabled_commands,
+			config_path: self.config_path,
+			..current.clone()
+		}
+	}
+
+	pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+		let path = path.as_ref();
+		let source = fs::read_to_string(path)
+			.into_diagnostic()
+			.wrap_err_with(|| format!("failed to read config file `{}`", path.display()))?;
+
+		let mut config = Self {
+			config_path: Some(path.to_path_buf()),
+			..Self::default()
+		};
+
+		file::apply(path, &source, &mut config)?;
+
+		Ok(config)
+	}
+
+	pub fn application_id() -> Result<Id<ApplicationMarker>> {
+		unsafe {
+			if let Some(id) = APPLICATION_ID {
+				return Ok(id);
+			}
+		}
+
+		let token = Self::token().into_diagnostic()?;
+
+		let first_part = token.split('.').next().unwrap_or_default();
+
+		let decoded = base64::decode(first_part).into_diagnostic()?;
+
+		let value = unsafe { String::from_utf8_unchecked(decoded) }
+			.parse()
+			.into_diagnostic()?;
+
+		unsafe { APPLICATION_ID = Id::new_checked(value) };
+
+		Ok(unsafe { Id::new_unchecked(value) })
+	}
+
+	fn parse_owners(matches: &ArgMatches) -> Result<Option<Vec<Id<UserMarker>>>, ClapError> {
+		if !matches.is_present(OWNERS) {
+			return Ok(None);
+		}
+
+		matches
+			.values_of_t::<u64>(OWNERS)?
+			.into_iter()
+			.map(|id| {
+				Id::new_checked(id).ok_or_else(|| {
+					ClapError::raw(clap::ErrorKind::ValueValidation, "owner IDs can't be 0\n")
+				})
+			})
+			.collect::<Result<_, _>>()
+			.map(Some)
+	}
+
+	// without a shard count, twilight picks the number of shards discord recommends
+	fn parse_shards(matches: &ArgMatches) -> Result<Option<ShardRange>, ClapError> {
+		let total = match value_of::<u64>(matches, SHARD_COUNT)? {
+			Some(0) => {
+				return Err(ClapError::raw(
+					clap::ErrorKind::ValueValidation,
+					"the shard count must be at least 1\n",
+				))
+			}
+			Some(total) => total,
+			None => return Ok(None),
+		};
+
+		let range = match matches.value_of(SHARD_RANGE) {
+			Some(range) => ShardRange::parse(range, total)?,
+			None => ShardRange {
+				from: 0,
+				to: total - 1,
+				total,
+			},
+		};
+
+		Ok(Some(range))
+	}
+
+	#[instrument]
+	pub fn token() -> Result<String, VarError> {
+		TOKEN.map_or_else(|| env::var("DISCORD_TOKEN"), |token| Ok(token.to_owned()))
+	}
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			guild_id: None,
+			remove_slash_commands: false,
+			database_path: PathBuf::from(DEFAULT_DATABASE_PATH),
+			cache_resource_types: ResourceType::all(),
+			owners: Vec::new(),
+			log_format: LogFormat::default(),
+			log_filter: None,
+			playground_url: DEFAULT_PLAYGROUND_URL.to_owned(),
+			playground_timeout: Duration::from_secs(DEFAULT_PLAYGROUND_TIMEOUT),
+			playground_user_agent: DEFAULT_USER_AGENT.to_owned(),
+			crates_io_url: DEFAULT_CRATES_IO_URL.to_owned(),
+			rate_limits: RateLimits::default(),
+			shards: None,
+			intents: None,
+			disabled_commands: Vec::new(),
+			config_path: None,
+		}
+	}
+}
+
+impl IntoApp for Config {
+	// none of the arguments have clap defaults, as those would replace whatever the config file set
+	fn into_app<'help>() -> App<'help> {
+		App::new(crate_name!())
+			.about(crate_description!())
+			.version(crate_version!())
+			.author(crate_authors!())
+			.args(&[
+				Arg::new(CONFIG)
+					.help("Config file to read, `starlight.toml` is used if it exists")
+					.env("STARLIGHT_CONFIG")
+					.long("config")
+					.short('c')
+					.takes_value(true),
+				Arg::new(GUILD_ID)
+					.help("Guild ID to use for testing")
+					.env("GUILD_ID")
+					.long("guild-id")
+					.short('g')
+					.takes_value(true),
+				Arg::new(REMOVE_SLASH_COMMANDS)
+					.help("Removes the global slash commands and exits")
+					.env("DELETE_SLASH_COMMANDS")
+					.long("delete-slash-commands"),
+				Arg::new(DATABASE_PATH)
+					.help("Directory the database is stored in")
+					.env("DATABASE_PATH")
+					.long("database-path")
+					.takes_value(true),
+				Arg::new(CACHE_RESOURCE_TYPES)
+					.help("Resources to cache, as a comma separated list of names like `MEMBER` (defaults to all of them)")
+					.env("CACHE_RESOURCE_TYPES")
+					.long("cache-resource-types")
+					.takes_value(true)
+					.multiple_occurrences(true)
+					.use_delimiter(true),
+				Arg::new(OWNERS)
+					.help("User IDs of the bot owners, as a comma separated list")
+					.env("OWNERS")
+					.long("owners")
+					.takes_value(true)
+					.multiple_occurrences(true)
+					.use_delimiter(true),
+				Arg::new(LOG_FORMAT)
+					.help("How logs are formatted, one of `pretty`, `compact` or `full`")
+					.env("LOG_FORMAT")
+					.long("log-format")
+					.takes_value(true),
+				Arg::new(LOG_FILTER)
+					.help("Which logs are shown, using `tracing` directives")
+					.env("RUST_LOG")
+					.long("log-filter")
+					.takes_value(true)
+					.validator(|filter| EnvFilter::try_new(filter)),
+				Arg::new(PLAYGROUND_URL)
+					.help("Base URL of the Rust playground to send code to")
+					.env("PLAYGROUND_URL")
+					.long("playground-url")
+					.takes_value(true)
+					.validator(Url::parse),
+				Arg::new(PLAYGROUND_TIMEOUT)
+					.help("Timeout in seconds for requests to the playground")
+					.env("PLAYGROUND_TIMEOUT")
+					.long("playground-timeout")
+					.takes_value(true),
+				Arg::new(PLAYGROUND_USER_AGENT)
+					.help("User agent to send with requests to the playground")
+					.env("PLAYGROUND_USER_AGENT")
+					.long("playground-user-agent")
+					.takes_value(true),
+				Arg::new(CRATES_IO_URL)
+					.help("Base URL of the crates.io API")
+					.env("CRATES_IO_URL")
+					.long("crates-io-url")
+					.takes_value(true)
+					.validator(Url::parse),
+				Arg::new(USER_RATE_LIMIT)
+					.help("Playground requests each user can make, as `requests/seconds`")
+					.env("USER_RATE_LIMIT")
+					.long("user-rate-limit")
+					.takes_value(true),
+				Arg::new(GUILD_RATE_LIMIT)
+					.help("Playground requests each server can make, as `requests/seconds`")
+					.env("GUILD_RATE_LIMIT")
+					.long("guild-rate-limit")
+					.takes_value(true),
+				Arg::new(SHARD_COUNT)
+					.help("Total number of shards across every process")
+					.env("SHARD_COUNT")
+					.long("shard-count")
+					.takes_value(true),
+				Arg::new(SHARD_RANGE)
+					.help("Shards to run in this process, as `from-to` (defaults to all of them)")
+					.env("SHARD_RANGE")
+					.long("shard-range")
+					.takes_value(true)
+					.requires(SHARD_COUNT),
+				Arg::new(INTENTS)
+					.help("Extra gateway intents to connect with, as a comma separated list of names like `GUILD_MESSAGES`, on top of the ones the commands need")
+					.env("INTENTS")
+					.long("intents")
+					.takes_value(true)
+					.multiple_occurrences(true)
+					.use_delimiter(true),
+				Arg::new(DISABLED_COMMANDS)
+					.help("Commands to turn off, as a comma separated list of names like `eval`")
+					.env("DISABLED_COMMANDS")
+					.long("disabled-commands")
+					.takes_value(true)
+					.multiple_occurrences(true)
+					.use_delimiter(true),
+			])
+	}
+
+	fn into_app_for_update<'help>() -> App<'help> {
+		Self::into_app()
+	}
+}
+
+impl FromArgMatches for Config {
+	fn from_arg_matches(matches: &ArgMatches) -> Result<Self, ClapError> {
+		let mut config = Self::default();
+
+		config.update_from_arg_matches(matches)?;
+
+		Ok(config)
+	}
+
+	fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), ClapError> {
+		if cfg!(debug_assertions) {
+			if let Some(guild_id) = value_of::<u64>(matches, GUILD_ID)? {
+				self.guild_id = Id::new_checked(guild_id);
+			}
+		}
+
+		if matches.is_present(REMOVE_SLASH_COMMANDS) {
+			self.remove_slash_commands = true;
+		}
+
+		if let Some(path) = matches.value_of(DATABASE_PATH) {
+			self.database_path = PathBuf::from(path);
+		}
+
+		if let Some(names) = matches.values_of(CACHE_RESOURCE_TYPES) {
+			self.cache_resource_types = flags_from_names(
+				names,
+				&RESOURCE_TYPE_NAMES,
+				ResourceType::empty(),
+				"cache resource type",
+			)?;
+		}
+
+		if let Some(owners) = Self::parse_owners(matches)? {
+			self.owners = owners;
+		}
+
+		if let Some(log_format) = value_of::<LogFormat>(matches, LOG_FORMAT)? {
+			self.log_format = log_format;
+		}
+
+		if let Some(log_filter) = matches.value_of(LOG_FILTER) {
+			self.log_filter = Some(log_filter.to_owned());
+		}
+
+		if let Some(url) = matches.value_of(PLAYGROUND_URL) {
+			self.playground_url = url.trim_end_matches('/').to_owned();
+		}
+
+		if let Some(secs) = value_of::<u64>(matches, PLAYGROUND_TIMEOUT)? {
+			self.playground_timeout = Duration::from_secs(secs);
+		}
+
+		if let Some(user_agent) = matches.value_of(PLAYGROUND_USER_AGENT) {
+			self.playground_user_agent = user_agent.to_owned();
+		}
+
+		if let Some(url) = matches.value_of(CRATES_IO_URL) {
+			self.crates_io_url = url.trim_end_matches('/').to_owned();
+		}
+
+		if let Some(rate_limit) = value_of::<RateLimit>(matches, USER_RATE_LIMIT)? {
+			self.rate_limits.user = rate_limit;
+		}
+
+		if let Some(rate_limit) = value_of::<RateLimit>(matches, GUILD_RATE_LIMIT)? {
+			self.rate_limits.guild = rate_limit;
+		}
+
+		if let Some(shards) = Self::parse_shards(matches)? {
+			self.shards = Some(shards);
+		}
+
+		if let Some(names) = matches.values_of(INTENTS) {
+			self.intents = Some(flags_from_names(
+				names,
+				&INTENT_NAMES,
+				Intents::empty(),
+				"gateway intent",
+			)?);
+		}
+
+		if let Some(names) = matches.values_of(DISABLED_COMMANDS) {
+			self.disabled_commands = names
+				.filter(|name| !name.trim().is_empty())
+				.map(|name| {
+					command_name(name).map(ToOwned::to_owned).ok_or_else(|| {
+						ClapError::raw(
+							clap::ErrorKind::ValueValidation,
+							format!("`{}` isn't a known command\n", name),
+						)
+					})
+				})
+				.collect::<Result<_, _>>()?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Parser for Config {}
+
+#[cfg(test)]
+mod tests {
+	use std::env;
+
+	use clap::{FromArgMatches, IntoApp};
+	use twilight_gateway::Intents;
+
+	use super::Config;
+	use crate::helpers::rate_limit::RateLimit;
+
+	#[test]
+	fn parse_intents() {
+		let matches = Config::into_app()
+			.try_get_matches_from([
+				"starlight",
+				"--intents",
+				"guilds,GUILD_MESSAGES,direct-messages",
+			])
+			.unwrap();
+
+		assert_eq!(
+			Config::from_arg_matches(&matches).unwrap().intents,
+			Some(Intents::GUILDS | Intents::GUILD_MESSAGES | Intents::DIRECT_MESSAGES)
+		);
+
+		let matches = Config::into_app()
+			.try_get_matches_from(["starlight", "--intents", "GUILD_SPEAKING"])
+			.unwrap();
+
+		assert!(Config::from_arg_matches(&matches).is_err());
+	}
+
+	#[test]
+	fn reload_keeps_startup_settings() {
+		let current = Config::default();
+		let reloaded = Config {
+			intents: Some(Intents::GUILDS),
+			crates_io_url: "https://example.com".to_owned(),
+			..Config::default()
+		}
+		.with_startup_settings(&current);
+
+		assert_eq!(reloaded.intents, None);
+		assert_eq!(reloaded.crates_io_url, "https://example.com");
+	}
+
+	#[test]
+	fn reload_rejects_missing_file() {
+		let current = Config {
+			config_path: Some(env::temp_dir().join("starlight-missing-config.toml")),
+			..Config::default()
+		};
+
+		assert!(current
+			.reload()
+			.unwrap_err()
+			.to_string()
+			.contains("doesn't exist anymore"));
+	}
+
+	#[test]
+	fn arguments_override_existing_values() {
+		let mut config = Config {
+			playground_user_agent: "from-file".to_owned(),
+			crates_io_url: "https://example.com".to_owned(),
+			..Config::default()
+		};
+
+		let matches = Config::into_app()
+			.try_get_matches_from(["starlight", "--user-rate-limit", "1/10"])
+			.unwrap();
+
+		config.update_from_arg_matches(&matches).unwrap();
+
+		assert_eq!(config.rate_limits.user, RateLimit::new(1, 10).unwrap());
+		assert_eq!(config.playground_user_agent, "from-file");
+		assert_eq!(config.crates_io_url, "https://example.com");
+	}
+}
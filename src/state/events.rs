@@ -2,7 +2,7 @@ use std::convert::Infallible;
 
 use starchart::{action::CreateEntryAction, Action, Result as ChartResult};
 use tracing::{event, Level};
-use twilight_gateway::Event;
+use twilight_gateway::{Event, Intents};
 use twilight_model::{
 	application::interaction::Interaction,
 	gateway::payload::incoming::{InteractionCreate, Ready},
@@ -15,9 +15,12 @@ use crate::{
 	settings::{GuildSettings, Tables},
 };
 
+pub(super) const INTENTS: Intents = Intents::GUILDS;
+
 // these should all be the same caller context, taking a `Context` as the first parameter, and whatever the event content is in the second.
 // however, they should return as strict of an error type as possible, using `Infallible` whevever possible (for more optimizations).
-pub(super) async fn handle(context: Context, event: Event) {
+#[instrument(skip(context, event))]
+pub(super) async fn handle(context: Context, shard_id: u64, event: Event) {
 	if let Err(e) = match event {
 		Event::Ready(e) => ready(context, *e).await.into_diagnostic(),
 		Event::GuildCreate(e) => guild_create(context, (*e).0).await.into_diagnostic(),
@@ -25,6 +28,14 @@ pub(super) async fn handle(context: Context, event: Event) {
 			interaction_create(context, *e).await;
 			Ok(())
 		}
+		Event::ShardConnected(_) => {
+			event!(Level::INFO, "shard connected");
+			Ok(())
+		}
+		Event::ShardDisconnected(_) => {
+			event!(Level::WARN, "shard disconnected");
+			Ok(())
+		}
 		_ => Ok(()),
 	} {
 		event!(Level::ERROR, "error occurred: {:?}", e);
@@ -59,7 +70,13 @@ async fn interaction_create(context: Context, interaction: InteractionCreate) {
 		Interaction::ApplicationCommand(cmd) | Interaction::ApplicationCommandAutocomplete(cmd) => {
 			context.helpers().interactions().handle(*cmd).await;
 		}
-		Interaction::MessageComponent(_) => {}
+		Interaction::MessageComponent(component) => {
+			context
+				.helpers()
+				.interactions()
+				.handle_component(*component)
+				.await;
+		}
 		i => event!(Level::WARN, ?i, "unhandled interaction"),
 	}
 }
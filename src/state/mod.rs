@@ -1,31 +1,51 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+	ops::Deref,
+	sync::{Arc, RwLock as SyncRwLock},
+	time::Duration,
+};
 
 use futures_util::StreamExt;
 use starchart::Starchart;
+use tokio::{sync::RwLock, time::timeout};
 use tracing::{event, Level};
 use twilight_cache_inmemory::InMemoryCache as Cache;
-use twilight_gateway::{shard::Events, Event, Shard};
+use twilight_gateway::{cluster::Events, Cluster, Event};
 use twilight_http::{client::InteractionClient, Client as HttpClient};
 use twilight_standby::Standby;
 
 use self::events::handle;
-pub use self::{builder::ContextBuilder, config::Config};
-use crate::{helpers::Helpers, prelude::*, settings::Tables};
+pub use self::{
+	builder::ContextBuilder,
+	config::{Config, ConfigFileError, LogFormat, ShardRange},
+};
+use crate::{
+	helpers::{
+		playground::{PlaygroundClient, PlaygroundEndpoint},
+		rate_limit::RateLimiter,
+		Helpers,
+	},
+	prelude::*,
+	settings::Tables,
+};
 
 mod builder;
 mod config;
 mod events;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Context(pub &'static State);
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Context(Arc<State>);
 
 impl Context {
-	pub async fn connect(self) -> Result<()> {
+	pub async fn connect(&self) -> Result<()> {
 		let id = Config::application_id()?;
 		let interaction_client = self.http.interaction(id);
 
-		if self.0.config.remove_slash_commands {
-			if let Some(guild_id) = self.0.config.guild_id {
+		let config = self.config();
+
+		if config.remove_slash_commands {
+			if let Some(guild_id) = config.guild_id {
 				event!(Level::INFO, %guild_id, "removing all slash commands in guild");
 				interaction_client
 					.set_guild_commands(guild_id, &[])
@@ -48,32 +68,96 @@ impl Context {
 
 		Tables::init(self).await.into_diagnostic()?;
 
-		self.0.shard.start().await.into_diagnostic()?;
-		event!(Level::INFO, "shard connected");
+		self.cluster.up().await;
+		event!(
+			Level::INFO,
+			shards = self.cluster.info().len(),
+			"cluster connected"
+		);
 
 		Ok(())
 	}
 
-	pub async fn process(self, mut events: Events) {
+	// every spawned handler holds a read guard, so taking the write lock waits for all of them to finish
+	pub async fn process(&self, mut events: Events) {
 		event!(Level::INFO, "started main event stream loop");
-		while let Some(val) = events.next().await {
+		while let Some((shard_id, val)) = events.next().await {
 			self.handle_event(&val);
-			tokio::spawn(handle(self, val));
+
+			let guard = Arc::clone(&self.handlers).read_owned().await;
+			let context = self.clone();
+
+			tokio::spawn(async move {
+				handle(context, shard_id, val).await;
+				drop(guard);
+			});
 		}
 		event!(Level::ERROR, "event stream exhausted (shouldn't happen)");
 	}
 
-	pub const fn helpers(self) -> Helpers {
-		Helpers::new(self)
+	pub fn helpers(&self) -> Helpers {
+		Helpers::new(self.clone())
 	}
 
-	pub fn shutdown(self) {
-		self.0.shard.shutdown();
+	// everything that can fail happens here, so applying the reload can't leave it half done
+	pub fn prepare_reload(&self, config: Config) -> Result<ConfigReload<'_>> {
+		let endpoint = PlaygroundEndpoint::from_config(&config)?;
+
+		Ok(ConfigReload {
+			context: self,
+			config,
+			endpoint,
+		})
+	}
+
+	pub async fn shutdown(self) {
+		self.cluster.down();
+		event!(Level::INFO, "cluster stopped");
+
+		if timeout(DRAIN_TIMEOUT, self.handlers.write()).await.is_err() {
+			event!(
+				Level::WARN,
+				"event handlers didn't finish within {:?}",
+				DRAIN_TIMEOUT
+			);
+		} else {
+			event!(Level::INFO, "event handlers drained");
+		}
+
+		// handlers that outlived the timeout keep their own clone, and the state goes when they do
+		let remaining = Arc::strong_count(&self.0) - 1;
+
+		if remaining > 0 {
+			event!(
+				Level::WARN,
+				remaining,
+				"the state is still in use by handlers that didn't finish"
+			);
+		}
+
+		drop(self);
+		event!(Level::INFO, "shutdown complete");
 	}
 
 	pub fn handle_event(&self, event: &Event) {
-		self.0.cache.update(event);
-		self.0.standby.process(event);
+		self.cache.update(event);
+		self.standby.process(event);
+	}
+}
+
+#[derive(Debug)]
+#[must_use = "a config reload does nothing until it's applied"]
+pub struct ConfigReload<'a> {
+	context: &'a Context,
+	config: Config,
+	endpoint: PlaygroundEndpoint,
+}
+
+impl ConfigReload<'_> {
+	pub fn apply(self) {
+		self.context.playground.reconfigure(self.endpoint);
+
+		*self.context.config.write().unwrap() = Arc::new(self.config);
 	}
 }
 
@@ -81,7 +165,7 @@ impl Deref for Context {
 	type Target = State;
 
 	fn deref(&self) -> &Self::Target {
-		self.0
+		&self.0
 	}
 }
 
@@ -89,11 +173,14 @@ impl Deref for Context {
 pub struct State {
 	cache: Arc<Cache>,
 	cdn: reqwest::Client,
-	shard: Arc<Shard>,
+	playground: PlaygroundClient,
+	rate_limiter: Arc<RateLimiter>,
+	cluster: Arc<Cluster>,
 	http: Arc<HttpClient>,
 	standby: Arc<Standby>,
-	config: Config,
+	config: Arc<SyncRwLock<Arc<Config>>>,
 	database: Starchart<TomlBackend>,
+	handlers: Arc<RwLock<()>>,
 }
 
 impl State {
@@ -103,8 +190,8 @@ impl State {
 	}
 
 	#[must_use]
-	pub fn shard(&self) -> &Shard {
-		&*self.shard
+	pub fn cluster(&self) -> &Cluster {
+		&*self.cluster
 	}
 
 	#[must_use]
@@ -117,14 +204,25 @@ impl State {
 		&self.cdn
 	}
 
+	#[must_use]
+	pub const fn playground(&self) -> &PlaygroundClient {
+		&self.playground
+	}
+
+	#[must_use]
+	pub fn rate_limiter(&self) -> &RateLimiter {
+		&*self.rate_limiter
+	}
+
 	#[must_use]
 	pub fn standby(&self) -> &Standby {
 		&*self.standby
 	}
 
+	// a snapshot, so a reload doesn't change the config halfway through handling something
 	#[must_use]
-	pub const fn config(&self) -> Config {
-		self.config
+	pub fn config(&self) -> Arc<Config> {
+		Arc::clone(&self.config.read().unwrap())
 	}
 
 	#[must_use]
@@ -139,37 +237,86 @@ impl State {
 }
 
 pub trait QuickAccess {
-	fn context(&self) -> Context;
+	fn context(&self) -> &Context;
 
 	fn cache(&self) -> &Cache {
-		self.context().0.cache()
+		self.context().cache()
 	}
 
-	fn shard(&self) -> &Shard {
-		self.context().0.shard()
+	fn cluster(&self) -> &Cluster {
+		self.context().cluster()
 	}
 
 	fn http(&self) -> &HttpClient {
-		self.context().0.http()
+		self.context().http()
 	}
 
 	fn cdn(&self) -> &reqwest::Client {
-		self.context().0.cdn()
+		self.context().cdn()
+	}
+
+	fn playground(&self) -> &PlaygroundClient {
+		self.context().playground()
+	}
+
+	fn rate_limiter(&self) -> &RateLimiter {
+		self.context().rate_limiter()
 	}
 
 	fn standby(&self) -> &Standby {
-		self.context().0.standby()
+		self.context().standby()
 	}
 
-	fn config(&self) -> Config {
-		self.context().0.config()
+	fn config(&self) -> Arc<Config> {
+		self.context().config()
 	}
 
 	fn database(&self) -> &Starchart<TomlBackend> {
-		self.context().0.database()
+		self.context().database()
 	}
 
 	fn interaction_client(&self) -> InteractionClient<'_> {
-		self.context().0.interaction_client()
+		self.context().interaction_client()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use tempfile::TempDir;
+
+	use super::{Config, ContextBuilder, ShardRange};
+
+	// a fixed shard range means building the cluster doesn't need to ask discord for a shard count
+	#[tokio::test]
+	async fn build_and_shutdown_twice() {
+		for _ in 0..2 {
+			let database = TempDir::new().unwrap();
+			let config = Config {
+				shards: Some(ShardRange {
+					from: 0,
+					to: 0,
+					total: 1,
+				}),
+				..Config::default()
+			};
+
+			let (context, _events) = ContextBuilder::new()
+				.config(config)
+				.token("token".to_owned())
+				.cluster_builder(|builder| builder)
+				.unwrap()
+				.database_path(database.path())
+				.build()
+				.await
+				.unwrap();
+
+			let state = Arc::downgrade(&context.0);
+
+			context.shutdown().await;
+
+			assert!(state.upgrade().is_none());
+		}
 	}
 }
@@ -1,7 +1,7 @@
 #[cfg(feature = "docker")]
 use std::{io::Error as IoError, net::ToSocketAddrs};
 
-use crate::prelude::*;
+use crate::{helpers::parsing::CodeBlockError, prelude::*};
 
 #[derive(Debug, Error)]
 #[allow(missing_copy_implementations)]
@@ -84,6 +84,8 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
 #[derive(Debug, Clone, Copy)]
 pub enum DefaultMessages {
 	PermissionDenied,
+	MissingCodeBlock,
+	MalformedCodeBlock,
 }
 
 impl Display for DefaultMessages {
@@ -92,6 +94,19 @@ impl Display for DefaultMessages {
 			Self::PermissionDenied => {
 				f.write_str("you do not have permission to perform this action")
 			}
+			Self::MissingCodeBlock => f.write_str("that message doesn't contain a code block"),
+			Self::MalformedCodeBlock => f.write_str(
+				"the code block in that message is malformed, make sure it isn't empty and is closed with backticks",
+			),
+		}
+	}
+}
+
+impl From<CodeBlockError> for DefaultMessages {
+	fn from(error: CodeBlockError) -> Self {
+		match error {
+			CodeBlockError::Missing => Self::MissingCodeBlock,
+			CodeBlockError::Malformed => Self::MalformedCodeBlock,
 		}
 	}
 }